@@ -32,6 +32,9 @@ fn StartPage() -> impl View {
     let clicks = use_state(|| 0);
     let show = use_state(|| true);
     let yes = use_state(|| false);
+    let name = use_state(String::new);
+    let subscribe = use_state(|| false);
+    let name_focus = use_focus_ref();
 
     let todos = use_state(|| {
         let mut todos = vec![];
@@ -41,6 +44,15 @@ fn StartPage() -> impl View {
         todos
     });
 
+    for result in [
+        use_shortcut("+", move || todos.update(|todos| add_todo(todos, None))),
+        use_shortcut("g n", move || name_focus.focus()),
+    ] {
+        if let Err(error) = result {
+            kano::log(&error.to_string());
+        }
+    }
+
     view! {
         <kbc:layout>
             <paragraph>
@@ -50,7 +62,7 @@ fn StartPage() -> impl View {
             </paragraph>
             <paragraph>
                 <button
-                    on:click={move || {
+                    on:click={move |_| {
                         todos.update(|todos| {
                             add_todo(todos, None);
                         });
@@ -62,7 +74,7 @@ fn StartPage() -> impl View {
             <TodoList {todos} />
             <paragraph>
                 <button
-                    on:click={move || {
+                    on:click={move |_| {
                         clicks.update(|clicks| *clicks += 1);
                         show.toggle();
                     }}
@@ -70,7 +82,7 @@ fn StartPage() -> impl View {
                     "hide/show"
                 </button>
                 <button
-                    on:click={move || {
+                    on:click={move |_| {
                         clicks.update(|clicks| *clicks += 1);
                         yes.toggle();
                     }}
@@ -78,7 +90,21 @@ fn StartPage() -> impl View {
                     "yes/no"
                 </button>
             </paragraph>
-            <paragraph>"clicked " {Fmt(clicks)} " times"</paragraph>
+            <paragraph>{format!("clicked {clicks} times")}</paragraph>
+            <paragraph>
+                "Name: "
+                <kbc:text_input bind:value={name} focus:ref={name_focus} input:placeholder="your name" />
+                <kbc:checkbox bind:checked={subscribe} />
+                " subscribe "
+                <button on:click={move |_| name_focus.focus()}>"edit name"</button>
+            </paragraph>
+            <paragraph>
+                if name_focus.is_focused() {
+                    "editing name..."
+                } else if subscribe.get() {
+                    {format!("subscribed as {name}")}
+                }
+            </paragraph>
             <paragraph>
                 if show.get() {
                     <strong>"PRESENT"</strong>
@@ -108,7 +134,7 @@ fn TodoList(todos: State<Vec<Todo>>) -> impl View {
             <list_item>
                 {text.clone()}
 
-                " ("<button on:click={move || delete(id)}>"x"</button>")"
+                " ("<button on:click={move |_| delete(id)}>"x"</button>")"
             </list_item>
         }
         </kbc:unordered_list>
@@ -48,7 +48,7 @@ fn test_comp(
             if show_heading.unwrap_or(false) {
                 <h1>"This is Kano Web Component!"</h1>
             }
-            <button on:click={move || style_select.toggle()}>
+            <button on:click={move |_| style_select.toggle()}>
                 ..children
             </button>
         </html:section>
@@ -1,4 +1,4 @@
-use kano::attr::{Click, On, To};
+use kano::attr::{Bind, Change, Click, FocusAttr, Input, InputAttr, On, Submit, To};
 
 #[cfg(feature = "tui")]
 pub mod tui;
@@ -10,5 +10,11 @@ pub mod web;
 #[derive(kano::FromProperty)]
 pub enum KBCAttr {
     OnClick(On<Click>),
+    OnInput(On<Input>),
+    OnChange(On<Change>),
+    OnSubmit(On<Submit>),
     To(To),
+    Bind(Bind),
+    Focus(FocusAttr),
+    Input(InputAttr),
 }
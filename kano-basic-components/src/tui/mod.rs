@@ -1,8 +1,16 @@
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
-use kano::{prelude::platform::*, Empty};
+use kano::{
+    attr::{Bind, InputAttr},
+    prelude::platform::*,
+    view::Reactive,
+    Empty,
+};
 use kano_tui::{
-    component::{Component, ComponentData, Layout, StateKeyed, Style},
+    component::{
+        BoxDirection, BoxLayout, Component, ComponentData, Layout, StateKeyed, Style,
+        TextInputLayout,
+    },
     ratatui::style::{Color, Modifier},
     Tml, Tui,
 };
@@ -15,7 +23,60 @@ pub fn layout(_: impl Props<Empty>, children: impl Children<Tui, Tml>) -> impl V
             layout: Layout::Block,
             style: Default::default(),
         }),
-        on_click: None,
+        handlers: vec![],
+        bind: None,
+        focus: vec![],
+        children,
+    }
+}
+
+/// A box that shares its width between its children.
+pub fn row(_: impl Props<Empty>, children: impl Children<Tui, Tml>) -> impl View<Tui, Tml> {
+    Component {
+        data: data("row", || ComponentData {
+            layout: Layout::Box(BoxLayout {
+                direction: BoxDirection::Row,
+                ..Default::default()
+            }),
+            style: Default::default(),
+        }),
+        handlers: vec![],
+        bind: None,
+        focus: vec![],
+        children,
+    }
+}
+
+/// A box that shares its height between its children.
+pub fn column(_: impl Props<Empty>, children: impl Children<Tui, Tml>) -> impl View<Tui, Tml> {
+    Component {
+        data: data("column", || ComponentData {
+            layout: Layout::Box(BoxLayout {
+                direction: BoxDirection::Column,
+                ..Default::default()
+            }),
+            style: Default::default(),
+        }),
+        handlers: vec![],
+        bind: None,
+        focus: vec![],
+        children,
+    }
+}
+
+/// A box whose content scrolls vertically when it doesn't fit.
+pub fn scroll(_: impl Props<Empty>, children: impl Children<Tui, Tml>) -> impl View<Tui, Tml> {
+    Component {
+        data: data("scroll", || ComponentData {
+            layout: Layout::Box(BoxLayout {
+                scroll: true,
+                ..Default::default()
+            }),
+            style: Default::default(),
+        }),
+        handlers: vec![],
+        bind: None,
+        focus: vec![],
         children,
     }
 }
@@ -26,7 +87,9 @@ pub fn paragraph(_: impl Props<Empty>, children: impl Children<Tui, Tml>) -> imp
             layout: Layout::Paragraph,
             style: Default::default(),
         }),
-        on_click: None,
+        handlers: vec![],
+        bind: None,
+        focus: vec![],
         children,
     }
 }
@@ -40,7 +103,9 @@ pub fn strong(_: impl Props<Empty>, children: impl Children<Tui, Tml>) -> impl V
                 ..Default::default()
             },
         }),
-        on_click: None,
+        handlers: vec![],
+        bind: None,
+        focus: vec![],
         children,
     }
 }
@@ -49,10 +114,10 @@ pub fn button(
     mut props: impl Props<KBCAttr>,
     children: impl Children<Tui, Tml>,
 ) -> impl View<Tui, Tml> {
-    let_props!({ KBCAttr::OnClick(on_click), KBCAttr::To(to) } = props);
+    let_props!({ KBCAttr::OnClick(on_click), KBCAttr::To(to), KBCAttr::Focus([focus]) } = props);
 
     if let Some(To(location)) = to {
-        on_click = Some(on::click(move || {
+        on_click = Some(on::click(move |_| {
             kano::history::push(location.clone().into_owned());
         }));
     }
@@ -65,10 +130,12 @@ pub fn button(
                 fg: Some(StateKeyed {
                     normal: Color::Green,
                     focused: Color::Black,
+                    hovered: Color::Black,
                 }),
                 bg: Some(StateKeyed {
                     normal: Color::Black,
                     focused: Color::LightYellow,
+                    hovered: Color::Yellow,
                 }),
                 prefix: Some((
                     "[",
@@ -76,10 +143,12 @@ pub fn button(
                         fg: Some(StateKeyed {
                             normal: Color::Magenta,
                             focused: Color::Black,
+                            hovered: Color::Black,
                         }),
                         bg: Some(StateKeyed {
                             normal: Color::Black,
                             focused: Color::LightYellow,
+                            hovered: Color::Yellow,
                         }),
                         ..Default::default()
                     }),
@@ -90,17 +159,21 @@ pub fn button(
                         fg: Some(StateKeyed {
                             normal: Color::Magenta,
                             focused: Color::Black,
+                            hovered: Color::Black,
                         }),
                         bg: Some(StateKeyed {
                             normal: Color::Black,
                             focused: Color::LightYellow,
+                            hovered: Color::Yellow,
                         }),
                         ..Default::default()
                     }),
                 )),
             },
         }),
-        on_click,
+        handlers: on_click.map(Into::into).into_iter().collect(),
+        bind: None,
+        focus,
         children,
     }
 }
@@ -114,7 +187,9 @@ pub fn unordered_list(
             layout: Layout::Block,
             style: Style::default(),
         }),
-        on_click: None,
+        handlers: vec![],
+        bind: None,
+        focus: vec![],
         children,
     }
 }
@@ -134,13 +209,92 @@ pub fn list_item(_: impl Props<Empty>, children: impl Children<Tui, Tml>) -> imp
                 ..Style::default()
             },
         }),
-        on_click: None,
+        handlers: vec![],
+        bind: None,
+        focus: vec![],
         children,
     }
 }
 
+pub fn text_input(
+    mut props: impl Props<KBCAttr>,
+    _: impl Children<Tui, Tml>,
+) -> impl View<Tui, Tml> {
+    let_props!({
+        KBCAttr::Bind(bind),
+        KBCAttr::Focus([focus]),
+        KBCAttr::Input([input]),
+        KBCAttr::OnInput(on_input),
+        KBCAttr::OnChange(on_change),
+        KBCAttr::OnSubmit(on_submit)
+    } = props);
+
+    let mut layout = TextInputLayout::default();
+    for input in input {
+        match input {
+            InputAttr::Placeholder(placeholder) => layout.placeholder = Some(placeholder.into()),
+            InputAttr::Password(password) => layout.password = password,
+        }
+    }
+
+    let handlers = [
+        on_input.map(Into::into),
+        on_change.map(Into::into),
+        on_submit.map(Into::into),
+    ];
+
+    Component {
+        data: text_input_data(layout),
+        handlers: handlers.into_iter().flatten().collect(),
+        bind,
+        focus,
+        children: (),
+    }
+}
+
+pub fn checkbox(mut props: impl Props<KBCAttr>, _: impl Children<Tui, Tml>) -> impl View<Tui, Tml> {
+    let_props!({ KBCAttr::Bind(bind), KBCAttr::Focus([focus]) } = props);
+
+    let checked = match bind {
+        Some(Bind::Checked(checked)) => Some(checked),
+        _ => None,
+    };
+
+    Component {
+        data: data("checkbox", || ComponentData {
+            layout: Layout::Inline,
+            style: Style {
+                fg: Some(StateKeyed {
+                    normal: Color::Green,
+                    focused: Color::Black,
+                    hovered: Color::Black,
+                }),
+                bg: Some(StateKeyed {
+                    normal: Color::Black,
+                    focused: Color::LightYellow,
+                    hovered: Color::Yellow,
+                }),
+                ..Default::default()
+            },
+        }),
+        handlers: vec![],
+        bind,
+        focus,
+        children: (Reactive(move || {
+            match checked.map(|checked| checked.get()) {
+                Some(true) => "[x]",
+                _ => "[ ]",
+            }
+        }),),
+    }
+}
+
+/// Text input data by placeholder and whether the input is a password.
+type TextInputCache = HashMap<(Option<String>, bool), Rc<ComponentData>>;
+
 thread_local! {
     static DATA_CACHE: RefCell<HashMap<&'static str, Rc<ComponentData>>> = RefCell::new(HashMap::new());
+    static TEXT_INPUT_CACHE: RefCell<TextInputCache> = RefCell::new(HashMap::new());
 }
 
 fn data(key: &'static str, producer: impl FnOnce() -> ComponentData) -> Rc<ComponentData> {
@@ -151,3 +305,266 @@ fn data(key: &'static str, producer: impl FnOnce() -> ComponentData) -> Rc<Compo
             .clone()
     })
 }
+
+/// Inputs differ in layout, so their data is shared by inputs with the same layout.
+fn text_input_data(layout: TextInputLayout) -> Rc<ComponentData> {
+    TEXT_INPUT_CACHE.with_borrow_mut(|cache| {
+        cache
+            .entry((layout.placeholder.clone(), layout.password))
+            .or_insert_with(|| {
+                Rc::new(ComponentData {
+                    layout: Layout::TextInput(layout),
+                    style: Style {
+                        modifier: Some(StateKeyed::uniform(Modifier::UNDERLINED)),
+                        bg: Some(StateKeyed {
+                            normal: Color::Black,
+                            focused: Color::LightYellow,
+                            hovered: Color::Yellow,
+                        }),
+                        fg: Some(StateKeyed {
+                            normal: Color::White,
+                            focused: Color::Black,
+                            hovered: Color::Black,
+                        }),
+                        ..Default::default()
+                    },
+                })
+            })
+            .clone()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use kano::prelude::app::*;
+    use kano_tui::{
+        crossterm::event::{KeyCode, KeyModifiers},
+        headless::HeadlessTui,
+        ratatui::style::Modifier,
+    };
+
+    mod kbc {
+        pub use crate::tui::*;
+    }
+
+    /// `let` children are in scope for the children after them, and blocks scope their own.
+    #[test]
+    fn let_bindings_and_local_blocks() {
+        let tui = HeadlessTui::new(24, 6, || {
+            view! {
+                <kbc:paragraph>
+                    let label = String::from("hello");
+                    {label}
+                    {
+                        let count = label.len();
+                        " " {count.to_string()} " "
+                    }
+                    {format!("{label}!")}
+                    <>"/" {label}</>
+                </kbc:paragraph>
+            }
+        });
+        assert_eq!(Some("║ hello 5 hello!/hello ║"), tui.text().lines().nth(2));
+    }
+
+    /// Bindings are only cloned into the children that use them, so they need not be `Clone`.
+    #[test]
+    fn only_captured_bindings_are_cloned() {
+        struct NotClone;
+        #[derive(Clone)]
+        struct Labelled {
+            label: &'static str,
+        }
+
+        let tui = HeadlessTui::new(25, 6, || {
+            view! {
+                <kbc:paragraph>
+                    let label = NotClone;
+                    let _unused = &label;
+                    let item = Labelled { label: "field" };
+                    {String::from("{label} ")}
+                    {format!("{{label}} {}", item.label)}
+                </kbc:paragraph>
+            }
+        });
+        assert_eq!(Some("║ {label} {label} field ║"), tui.text().lines().nth(2));
+    }
+
+    /// Matches with more arms than the largest sum view nest sum views.
+    #[test]
+    fn matches_and_else_if_chains_with_many_arms() {
+        let tui = HeadlessTui::new(24, 6, || {
+            let number = 13;
+            view! {
+                <kbc:paragraph>
+                    match number {
+                        0 => "0" 1 => "1" 2 => "2" 3 => "3" 4 => "4" 5 => "5" 6 => "6"
+                        7 => "7" 8 => "8" 9 => "9" 10 => "10" 11 => "11" 12 => "12"
+                        13 => "thirteen"
+                        _ => "many"
+                    }
+                    " "
+                    if number == 0 { "0" } else if number == 1 { "1" } else if number == 2 { "2" }
+                    else if number == 3 { "3" } else if number == 4 { "4" } else if number == 5 { "5" }
+                    else if number == 6 { "6" } else if number == 7 { "7" } else if number == 8 { "8" }
+                    else if number == 9 { "9" } else if number == 10 { "10" } else if number == 11 { "11" }
+                    else if number == 12 { "12" } else if number == 13 { "13" } else { "many" }
+                </kbc:paragraph>
+            }
+        });
+        assert_eq!(Some("║ thirteen 13          ║"), tui.text().lines().nth(2));
+    }
+
+    #[test]
+    fn typing_into_text_inputs() {
+        let mut tui = HeadlessTui::new(26, 6, || {
+            let name = use_state(String::new);
+            let secret = use_state(String::new);
+            let submitted = use_state(String::new);
+            view! {
+                <kbc:paragraph>
+                    <kbc:text_input
+                        bind:value={name}
+                        input:placeholder="name"
+                        on:submit={move |_| submitted.set(name.get())}
+                    />
+                    " "
+                    <kbc:text_input bind:value={secret} input:password={true} />
+                    " "
+                    {submitted.get()}
+                </kbc:paragraph>
+            }
+        });
+        assert_eq!(
+            Some("║ name                   ║"),
+            tui.text().lines().nth(2)
+        );
+        assert!(tui.buffer().get(2, 2).modifier.contains(Modifier::DIM));
+
+        tui.type_text("kano");
+        tui.press(KeyCode::Enter);
+        tui.press(KeyCode::Tab);
+        tui.type_text("pw");
+        assert_eq!(
+            Some("║ kano  ••  kano         ║"),
+            tui.text().lines().nth(2)
+        );
+    }
+
+    #[test]
+    fn rows_columns_and_scrolling() {
+        let mut tui = HeadlessTui::new(30, 8, || {
+            view! {
+                <kbc:row>
+                    <kbc:column>
+                        <kbc:paragraph>"top"</kbc:paragraph>
+                        <kbc:paragraph>"bottom"</kbc:paragraph>
+                    </kbc:column>
+                    <kbc:scroll>
+                        for line in vec![0, 1, 2, 3, 4] {
+                            <kbc:paragraph>{format!("line {line}")}</kbc:paragraph>
+                        }
+                        <kbc:button on:click={|_| {}}>"end"</kbc:button>
+                    </kbc:scroll>
+                </kbc:row>
+            }
+        });
+        // The scroll box reveals the focused button
+        let lines: Vec<String> = tui.text().lines().map(String::from).collect();
+        assert_eq!("║ top          line 1      ▲ ║", lines[2]);
+        assert_eq!("║ bottom       line 2      █ ║", lines[3]);
+        assert_eq!("║              line 4[end] ▼ ║", lines[5]);
+
+        tui.press(KeyCode::PageUp);
+        assert_eq!(
+            Some("║ top          line 0      ▲ ║"),
+            tui.text().lines().nth(2)
+        );
+    }
+
+    #[test]
+    fn backspace_in_an_empty_input_does_not_navigate_back() {
+        let mut tui = HeadlessTui::new(26, 6, || {
+            let name = use_state(String::new);
+            view! {
+                <kbc:paragraph>
+                    <kbc:text_input bind:value={name} input:placeholder="name" />
+                    " "
+                    {kano::history::current_location()}
+                </kbc:paragraph>
+            }
+        });
+        kano::history::push("/page".into());
+        tui.dispatch();
+        assert_eq!(
+            Some("║ name  /page            ║"),
+            tui.text().lines().nth(2)
+        );
+
+        tui.press(KeyCode::Backspace);
+        tui.press(KeyCode::Delete);
+        assert_eq!(
+            Some("║ name  /page            ║"),
+            tui.text().lines().nth(2)
+        );
+    }
+
+    /// Bound inputs show changes to their state, even when no enclosing view reads it.
+    #[test]
+    fn bound_inputs_follow_their_state() {
+        let mut tui = HeadlessTui::new(26, 6, || {
+            let name = use_state(String::new);
+            let checked = use_state(|| false);
+            use_shortcut("Ctrl+r", move || {
+                name.set("reset".into());
+                checked.set(true);
+            })
+            .unwrap();
+            view! {
+                <kbc:paragraph>
+                    <kbc:text_input bind:value={name} />
+                    " "
+                    <kbc:checkbox bind:checked={checked} />
+                </kbc:paragraph>
+            }
+        });
+        tui.type_text("kano");
+        assert_eq!(
+            Some("║ kano  [ ]              ║"),
+            tui.text().lines().nth(2)
+        );
+
+        tui.press_with(KeyCode::Char('r'), KeyModifiers::CONTROL);
+        assert_eq!(
+            Some("║ reset  [x]             ║"),
+            tui.text().lines().nth(2)
+        );
+    }
+
+    /// Inputs share their data with inputs of the same layout, so re-renders don't restyle them.
+    #[test]
+    fn inputs_with_the_same_layout_share_data() {
+        use std::rc::Rc;
+
+        use kano_tui::component::TextInputLayout;
+
+        let layout = |placeholder: &str, password| TextInputLayout {
+            placeholder: Some(placeholder.into()),
+            password,
+        };
+        let name = super::text_input_data(layout("name", false));
+
+        assert!(Rc::ptr_eq(
+            &name,
+            &super::text_input_data(layout("name", false))
+        ));
+        assert!(!Rc::ptr_eq(
+            &name,
+            &super::text_input_data(layout("other", false))
+        ));
+        assert!(!Rc::ptr_eq(
+            &name,
+            &super::text_input_data(layout("name", true))
+        ));
+    }
+}
@@ -1,4 +1,8 @@
-use kano::{attr::To, prelude::platform::*, Children, Empty, View};
+use kano::{
+    attr::{FocusAttr, InputAttr, To},
+    prelude::platform::*,
+    Children, Empty, View,
+};
 use kano_html::{attr::*, html, Html5};
 use kano_web::Web;
 
@@ -10,6 +14,27 @@ pub fn layout(_: impl Props<Empty>, children: impl Children<Web, Html5>) -> impl
     }
 }
 
+/// A box that shares its width between its children.
+pub fn row(_: impl Props<Empty>, children: impl Children<Web, Html5>) -> impl View<Web, Html5> {
+    view! {
+        <html:div style="display: flex; flex-direction: row">..children</html:div>
+    }
+}
+
+/// A box that shares its height between its children.
+pub fn column(_: impl Props<Empty>, children: impl Children<Web, Html5>) -> impl View<Web, Html5> {
+    view! {
+        <html:div style="display: flex; flex-direction: column">..children</html:div>
+    }
+}
+
+/// A box whose content scrolls vertically when it doesn't fit.
+pub fn scroll(_: impl Props<Empty>, children: impl Children<Web, Html5>) -> impl View<Web, Html5> {
+    view! {
+        <html:div style="overflow-y: auto">..children</html:div>
+    }
+}
+
 pub fn paragraph(
     _: impl Props<Empty>,
     children: impl Children<Web, Html5>,
@@ -29,11 +54,12 @@ pub fn button(
     mut props: impl Props<KBCAttr>,
     children: impl Children<Web, Html5>,
 ) -> impl View<Web, Html5> {
-    let_props!({ KBCAttr::OnClick(on_click), KBCAttr::To(to) } = props);
+    let_props!({ KBCAttr::OnClick(on_click), KBCAttr::To(to), KBCAttr::Focus([focus]) } = props);
+    let [focus_0, focus_1, focus_2, focus_3] = focus_props(focus);
 
     #[cfg(feature = "web-routing")]
     if let Some(To(location)) = to {
-        on_click = Some(on::click(move || {
+        on_click = Some(on::click(move |_| {
             kano::history::push(location.clone().into_owned());
         }));
     }
@@ -57,7 +83,16 @@ pub fn button(
     ";
 
     view! {
-        <html:button class={["kbc_button"]} style={button_style} ..href ..on_click>
+        <html:button
+            class={["kbc_button"]}
+            style={button_style}
+            ..href
+            ..on_click
+            ..focus_0
+            ..focus_1
+            ..focus_2
+            ..focus_3
+        >
             ..children
         </html:button>
     }
@@ -80,3 +115,151 @@ pub fn list_item(
         <html:li>..children</html:li>
     }
 }
+
+pub fn text_input(
+    mut props: impl Props<KBCAttr>,
+    _: impl Children<Web, Html5>,
+) -> impl View<Web, Html5> {
+    let_props!({
+        KBCAttr::Bind(bind),
+        KBCAttr::Focus([focus]),
+        KBCAttr::Input([input]),
+        KBCAttr::OnInput(on_input),
+        KBCAttr::OnChange(on_change),
+        KBCAttr::OnSubmit(on_submit)
+    } = props);
+    let [focus_0, focus_1, focus_2, focus_3] = focus_props(focus);
+
+    let mut input_type = "text";
+    let mut placeholder = None;
+    for input in input {
+        match input {
+            InputAttr::Placeholder(text) => placeholder = Some(kano_html::attr::placeholder(text)),
+            InputAttr::Password(true) => input_type = "password",
+            InputAttr::Password(false) => {}
+        }
+    }
+
+    // The input submits its own form on Enter, which browsers hold back while an IME is composing.
+    // The page is not reloaded, also without a submit handler.
+    let on_submit = Some(
+        on_submit
+            .unwrap_or_else(|| on::submit(|_| {}))
+            .prevent_default(),
+    );
+
+    view! {
+        <html:form style="display: contents" ..on_submit>
+            <html:input
+                type={input_type}
+                ..placeholder
+                ..bind
+                ..focus_0
+                ..focus_1
+                ..focus_2
+                ..focus_3
+                ..on_input
+                ..on_change
+            />
+        </html:form>
+    }
+}
+
+pub fn checkbox(
+    mut props: impl Props<KBCAttr>,
+    _: impl Children<Web, Html5>,
+) -> impl View<Web, Html5> {
+    let_props!({ KBCAttr::Bind(bind), KBCAttr::Focus([focus]) } = props);
+    let [focus_0, focus_1, focus_2, focus_3] = focus_props(focus);
+
+    view! {
+        <html:input type="checkbox" ..bind ..focus_0 ..focus_1 ..focus_2 ..focus_3 />
+    }
+}
+
+/// Focus properties forwarded to the underlying element, one of each kind.
+///
+/// A property given twice replaces the earlier one, like other attributes.
+fn focus_props(focus: Vec<FocusAttr>) -> [Option<FocusAttr>; 4] {
+    let mut props = [None; 4];
+    for focus in focus {
+        let index = match focus {
+            FocusAttr::Ref(_) => 0,
+            FocusAttr::Autofocus(_) => 1,
+            FocusAttr::Trap(_) => 2,
+            FocusAttr::TabIndex(_) => 3,
+        };
+        props[index] = Some(focus);
+    }
+    props
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use kano::{attr::FocusAttr, prelude::platform::*};
+    use kano_web::Web;
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_test::*;
+
+    mod kbc {
+        pub use crate::web::*;
+    }
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn focus_props_keeps_one_of_each_kind() {
+        let props = super::focus_props(vec![
+            FocusAttr::TabIndex(1),
+            FocusAttr::Autofocus(true),
+            FocusAttr::Trap(true),
+            FocusAttr::TabIndex(2),
+        ]);
+        assert_eq!(
+            [
+                None,
+                Some(FocusAttr::Autofocus(true)),
+                Some(FocusAttr::Trap(true)),
+                Some(FocusAttr::TabIndex(2))
+            ],
+            props
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn text_input_submits_its_form() {
+        let submitted = Rc::new(Cell::new(0));
+
+        kano::init::<Web>()
+            .run_app({
+                let submitted = submitted.clone();
+                move || {
+                    let submitted = submitted.clone();
+                    view! {
+                        <kbc:text_input
+                            input:placeholder="secret"
+                            input:password={true}
+                            on:submit={move |_| submitted.set(submitted.get() + 1)}
+                        />
+                    }
+                }
+            })
+            .unwrap();
+
+        let document = web_sys::window().unwrap().document().unwrap();
+        let input: web_sys::HtmlInputElement = document
+            .query_selector("form > input")
+            .unwrap()
+            .unwrap()
+            .unchecked_into();
+        assert_eq!("password", input.type_());
+        assert_eq!("secret", input.placeholder());
+
+        // Submitting does not reload the page, which would abort the test
+        let form: web_sys::HtmlFormElement = input.form().unwrap();
+        form.request_submit().unwrap();
+        assert_eq!(1, submitted.get());
+    }
+}
@@ -2,6 +2,39 @@ use std::borrow::Cow;
 
 use crate::properties::{Property, PropertyValue, StringOrBool, Strings};
 
+/// Directives toggling single classes, written `class:name={enabled}`.
+pub mod class {
+    use std::borrow::Cow;
+
+    use crate::properties::ClassToggle;
+
+    /// Toggle the class `name` on or off.
+    pub fn toggle(name: impl Into<Cow<'static, str>>, enabled: bool) -> ClassToggle {
+        ClassToggle {
+            name: name.into(),
+            enabled,
+        }
+    }
+}
+
+/// Directives setting single inline style properties, written `style:name={value}`.
+pub mod style {
+    use std::borrow::Cow;
+
+    use crate::properties::StyleProperty;
+
+    /// Set the style property `name` to `value`.
+    pub fn set(
+        name: impl Into<Cow<'static, str>>,
+        value: impl Into<Cow<'static, str>>,
+    ) -> StyleProperty {
+        StyleProperty {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+}
+
 macro_rules! define_attr {
     (($ident:ident, $name:literal, $idl:literal, STRING)) => {
         /// The
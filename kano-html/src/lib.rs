@@ -24,6 +24,10 @@ impl<A, C> HtmlElement<A, C> {
 pub enum HtmlAttribute {
     Attribute(properties::Property),
     Event(kano::attr::On<kano::attr::Event>),
+    Bind(kano::attr::Bind),
+    Class(properties::ClassToggle),
+    Style(properties::StyleProperty),
+    Focus(kano::attr::FocusAttr),
 }
 
 /// The Html5 markup language.
@@ -1,20 +1,38 @@
-use kano::attr::{Click, MouseOver, On};
+use kano::attr::*;
 
 use std::borrow::Cow;
 
 use crate::HtmlAttribute;
 
-impl kano::FromProperty<On<Click>> for HtmlAttribute {
-    fn from_property(property: On<Click>) -> Option<Self> {
-        Some(Self::Event(property.into()))
-    }
+macro_rules! event_properties {
+    ($($event:ident),+ $(,)?) => {
+        $(
+            impl kano::FromProperty<On<$event>> for HtmlAttribute {
+                fn from_property(property: On<$event>) -> Option<Self> {
+                    Some(Self::Event(property.into()))
+                }
+            }
+        )+
+    };
 }
 
-impl kano::FromProperty<On<MouseOver>> for HtmlAttribute {
-    fn from_property(property: On<MouseOver>) -> Option<Self> {
-        Some(Self::Event(property.into()))
-    }
-}
+event_properties!(
+    Click,
+    MouseOver,
+    MouseEnter,
+    MouseLeave,
+    PointerDown,
+    PointerUp,
+    PointerMove,
+    KeyDown,
+    KeyUp,
+    Input,
+    Change,
+    Submit,
+    Focus,
+    Blur,
+    Scroll,
+);
 
 #[derive(PartialEq)]
 pub struct Property {
@@ -28,6 +46,20 @@ impl Property {
     }
 }
 
+/// A single class, toggled on or off independently of the `class` attribute.
+#[derive(PartialEq)]
+pub struct ClassToggle {
+    pub name: Cow<'static, str>,
+    pub enabled: bool,
+}
+
+/// A single inline style property, set independently of the `style` attribute.
+#[derive(PartialEq)]
+pub struct StyleProperty {
+    pub name: Cow<'static, str>,
+    pub value: Cow<'static, str>,
+}
+
 #[derive(PartialEq)]
 pub enum PropertyValue {
     String(Cow<'static, str>),
@@ -8,6 +8,7 @@
 use std::fmt::Display;
 
 use syn::{
+    ext::IdentExt,
     parse::{Parse, ParseStream},
     spanned::Spanned,
 };
@@ -71,9 +72,12 @@ pub enum NodeKind {
     Spread(syn::Ident),
     Text(Text),
     TextExpr(syn::Expr),
+    Format(Format),
     Component(Component),
     Match(Match),
     For(For),
+    /// A `let` statement, scoped to the following siblings.
+    Let(syn::Local),
 }
 
 #[derive(Eq, PartialEq, Debug)]
@@ -94,12 +98,30 @@ impl TagName {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Attr {
     KeyValue(KeyValueAttr),
+    Directive(DirectiveAttr),
     Implicit(syn::Ident),
 }
 
+/// A `class:name={enabled}` or `style:name={value}` attribute,
+/// which targets a single class or style property of an element.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DirectiveAttr {
+    pub kind: DirectiveKind,
+    pub name: syn::LitStr,
+    pub value: AttrValue,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DirectiveKind {
+    Class,
+    Style,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct KeyValueAttr {
     pub key: syn::Path,
+    /// Methods called on the property, from modifiers like `on:submit|prevent`
+    pub modifiers: Vec<syn::Ident>,
     pub value: AttrValue,
 }
 
@@ -120,6 +142,40 @@ impl Parse for Text {
     }
 }
 
+/// Text interpolated using a format string, like [std::format].
+#[derive(Debug, Eq, PartialEq)]
+pub struct Format {
+    pub format: syn::LitStr,
+    pub args: Vec<syn::Expr>,
+}
+
+/// Whether the path is `format`, `std::format` or `::std::format`.
+fn is_format_macro(path: &syn::Path) -> bool {
+    let idents: Vec<String> = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    match path.leading_colon {
+        Some(_) => idents == ["std", "format"],
+        None => idents == ["format"] || idents == ["std", "format"],
+    }
+}
+
+/// The arguments of `format!`: a format string, then its arguments.
+fn parse_format_args(input: ParseStream) -> syn::Result<Format> {
+    let format = input.parse::<syn::LitStr>()?;
+    let mut args = vec![];
+    while !input.is_empty() {
+        input.parse::<syn::token::Comma>()?;
+        if input.is_empty() {
+            break;
+        }
+        args.push(input.parse::<syn::Expr>()?);
+    }
+    Ok(Format { format, args })
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub struct Element {
     pub path: syn::Path,
@@ -148,6 +204,7 @@ pub struct Match {
 #[derive(Debug, Eq, PartialEq)]
 pub struct MatchArm {
     pub pat: syn::Pat,
+    pub guard: Option<syn::Expr>,
     pub node: Node,
 }
 
@@ -196,10 +253,10 @@ impl Parse for Node {
 impl Parser {
     #[allow(unused)]
     pub fn parse_at_least_one(&self, input: ParseStream) -> syn::Result<Node> {
-        let mut nodes = vec![self.parse_node(input)?];
+        let mut nodes = vec![self.parse_child(input)?];
 
         while !input.is_empty() {
-            nodes.push(self.parse_node(input)?);
+            nodes.push(self.parse_child(input)?);
         }
 
         if nodes.len() == 1 {
@@ -212,6 +269,22 @@ impl Parser {
         }
     }
 
+    /// Parse one entry in a list of children, which may also be a `let` statement
+    /// scoped to the following siblings.
+    fn parse_child(&self, input: ParseStream) -> syn::Result<Node> {
+        if input.peek(syn::token::Let) {
+            return match input.parse::<syn::Stmt>()? {
+                syn::Stmt::Local(local) => Ok(Node {
+                    kind: NodeKind::Let(local),
+                    constant: true,
+                }),
+                stmt => Err(syn::Error::new(stmt.span(), "Expected `let` statement")),
+            };
+        }
+
+        self.parse_node(input)
+    }
+
     fn parse_node(&self, input: ParseStream) -> syn::Result<Node> {
         if input.peek(syn::token::Lt) {
             return self.parse_element_or_fragment(input);
@@ -259,8 +332,23 @@ impl Parser {
         let content;
         let _brace_token = syn::braced!(content in input);
 
+        // A local block: `{ let a = b; <node /> }`
+        if content.peek(syn::token::Let) {
+            return self.parse_fragment_content(&content);
+        }
+
         let expr: syn::Expr = content.parse()?;
 
+        // Interpolated text: `{format!("clicked {clicks} times")}` becomes a single text node
+        if let syn::Expr::Macro(syn::ExprMacro { mac, attrs }) = &expr {
+            if attrs.is_empty() && is_format_macro(&mac.path) {
+                return Ok(Node {
+                    kind: NodeKind::Format(mac.parse_body_with(parse_format_args)?),
+                    constant: false,
+                });
+            }
+        }
+
         Ok(Node {
             kind: NodeKind::TextExpr(expr),
             constant: false,
@@ -369,6 +457,12 @@ impl Parser {
                                     "Ident not supported here",
                                 ))
                             }
+                            Attr::Directive(directive) => {
+                                return Err(syn::Error::new(
+                                    directive.name.span(),
+                                    "Directives are not supported on components",
+                                ))
+                            }
                         }
                     }
 
@@ -396,7 +490,23 @@ impl Parser {
                 continue;
             }
 
+            if let Some(kind) = peek_directive(input) {
+                let _kind: syn::Ident = input.parse()?;
+                input.parse::<syn::token::Colon>()?;
+                let name = parse_directive_name(input)?;
+                let value = if input.peek(syn::token::Eq) {
+                    input.parse::<syn::token::Eq>()?;
+                    self.parse_attr_value(input)?
+                } else {
+                    AttrValue::ImplicitTrue
+                };
+
+                attrs.push(Attr::Directive(DirectiveAttr { kind, name, value }));
+                continue;
+            }
+
             let key = parse_path(input)?;
+            let modifiers = parse_modifiers(input)?;
             let value = if input.peek(syn::token::Eq) {
                 input.parse::<syn::token::Eq>()?;
                 self.parse_attr_value(input)?
@@ -404,7 +514,11 @@ impl Parser {
                 AttrValue::ImplicitTrue
             };
 
-            attrs.push(Attr::KeyValue(KeyValueAttr { key, value }));
+            attrs.push(Attr::KeyValue(KeyValueAttr {
+                key,
+                modifiers,
+                value,
+            }));
         }
 
         Ok(attrs)
@@ -430,7 +544,7 @@ impl Parser {
                 break;
             }
 
-            children.push(self.parse_node(input)?);
+            children.push(self.parse_child(input)?);
         }
 
         Ok(children)
@@ -441,9 +555,14 @@ impl Parser {
         let content;
         let _brace_token = syn::braced!(content in input);
 
+        self.parse_fragment_content(&content)
+    }
+
+    /// Parse the inside of a braced fragment
+    fn parse_fragment_content(&self, content: ParseStream) -> syn::Result<Node> {
         let mut nodes = vec![];
         while !content.is_empty() {
-            nodes.push(self.parse_node(&content)?);
+            nodes.push(self.parse_child(content)?);
         }
 
         if nodes.len() == 1 {
@@ -457,10 +576,21 @@ impl Parser {
     }
 
     fn parse_if(&self, input: ParseStream) -> syn::Result<Match> {
-        input.parse::<syn::token::If>()?;
-        let expr = syn::Expr::parse_without_eager_brace(input)?;
+        let mut branches = vec![];
+
+        loop {
+            input.parse::<syn::token::If>()?;
+            let expr = syn::Expr::parse_without_eager_brace(input)?;
+            let node = self.parse_braced_fragment(input)?;
 
-        let then_branch = self.parse_braced_fragment(input)?;
+            branches.push((expr, node));
+
+            if input.peek(syn::token::Else) && input.peek2(syn::token::If) {
+                input.parse::<syn::token::Else>()?;
+            } else {
+                break;
+            }
+        }
 
         let else_branch = if input.peek(syn::token::Else) {
             self.parse_else(input)?
@@ -471,37 +601,46 @@ impl Parser {
             }
         };
 
-        match expr {
-            syn::Expr::Let(the_let) => {
-                // transform into proper match
-                Ok(Match {
-                    expr: *the_let.expr,
-                    arms: vec![
-                        MatchArm {
-                            pat: *the_let.pat,
-                            node: then_branch,
-                        },
-                        MatchArm {
-                            pat: syn::parse_quote! { _ },
-                            node: else_branch,
-                        },
-                    ],
+        let has_let = branches
+            .iter()
+            .any(|(expr, _)| matches!(expr, syn::Expr::Let(_)));
+
+        if branches.len() > 1 && !has_let {
+            // A chain of plain conditions becomes one n-ary match with guards:
+            let mut arms: Vec<_> = branches
+                .into_iter()
+                .map(|(expr, node)| MatchArm {
+                    pat: syn::parse_quote! { _ },
+                    guard: Some(expr),
+                    node,
                 })
-            }
-            expr => Ok(Match {
-                expr,
-                arms: vec![
-                    MatchArm {
-                        pat: syn::parse_quote! { true },
-                        node: then_branch,
-                    },
-                    MatchArm {
-                        pat: syn::parse_quote! { false },
-                        node: else_branch,
-                    },
-                ],
-            }),
+                .collect();
+            arms.push(MatchArm {
+                pat: syn::parse_quote! { _ },
+                guard: None,
+                node: else_branch,
+            });
+
+            return Ok(Match {
+                expr: syn::parse_quote! { () },
+                arms,
+            });
+        }
+
+        // `if let` does not work as a guard, so nest the chain from the back:
+        let mut branches = branches.into_iter().rev();
+        let (expr, then_branch) = branches.next().unwrap();
+        let mut the_match = if_to_match(expr, then_branch, else_branch);
+
+        for (expr, then_branch) in branches {
+            let else_branch = Node {
+                kind: NodeKind::Match(the_match),
+                constant: false,
+            };
+            the_match = if_to_match(expr, then_branch, else_branch);
         }
+
+        Ok(the_match)
     }
 
     fn parse_else(&self, input: ParseStream) -> syn::Result<Node> {
@@ -509,12 +648,7 @@ impl Parser {
 
         let lookahead = input.lookahead1();
 
-        if input.peek(syn::token::If) {
-            Ok(Node {
-                kind: NodeKind::Match(self.parse_if(input)?),
-                constant: false,
-            })
-        } else if input.peek(syn::token::Brace) {
+        if input.peek(syn::token::Brace) {
             self.parse_braced_fragment(input)
         } else {
             Err(lookahead.error())
@@ -541,16 +675,18 @@ impl Parser {
         // BUG: This does not support OR-patterns
         let pat: syn::Pat = syn::Pat::parse_single(input)?;
 
-        // Guard
-        if input.peek(syn::token::If) {
-            panic!("Match if-guard not yet supported");
-        }
+        let guard = if input.peek(syn::token::If) {
+            input.parse::<syn::token::If>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
 
         input.parse::<syn::token::FatArrow>()?;
 
         let node = self.parse_node(input)?;
 
-        Ok(MatchArm { pat, node })
+        Ok(MatchArm { pat, guard, node })
     }
 
     fn parse_for(&self, input: ParseStream) -> syn::Result<For> {
@@ -570,6 +706,42 @@ impl Parser {
     }
 }
 
+/// Transform a single `if` (or `if let`) into a two-armed match.
+fn if_to_match(expr: syn::Expr, then_branch: Node, else_branch: Node) -> Match {
+    match expr {
+        syn::Expr::Let(the_let) => Match {
+            expr: *the_let.expr,
+            arms: vec![
+                MatchArm {
+                    pat: *the_let.pat,
+                    guard: None,
+                    node: then_branch,
+                },
+                MatchArm {
+                    pat: syn::parse_quote! { _ },
+                    guard: None,
+                    node: else_branch,
+                },
+            ],
+        },
+        expr => Match {
+            expr,
+            arms: vec![
+                MatchArm {
+                    pat: syn::parse_quote! { true },
+                    guard: None,
+                    node: then_branch,
+                },
+                MatchArm {
+                    pat: syn::parse_quote! { false },
+                    guard: None,
+                    node: else_branch,
+                },
+            ],
+        },
+    }
+}
+
 pub fn parse_tag_name(input: ParseStream) -> Result<TagName, syn::Error> {
     let path = parse_path(input)?;
 
@@ -585,6 +757,63 @@ pub fn parse_tag_name(input: ParseStream) -> Result<TagName, syn::Error> {
     }
 }
 
+/// Parse modifiers like `|prevent|stop` into the names of the methods they call
+fn parse_modifiers(input: ParseStream) -> syn::Result<Vec<syn::Ident>> {
+    let mut modifiers = vec![];
+
+    while input.peek(syn::token::Or) {
+        input.parse::<syn::token::Or>()?;
+        let modifier: syn::Ident = input.parse()?;
+        let method = match modifier.to_string().as_str() {
+            "prevent" => "prevent_default",
+            "stop" => "stop_propagation",
+            "once" => "once",
+            "capture" => "capture",
+            "delegate" => "delegate",
+            _ => {
+                return Err(syn::Error::new(
+                    modifier.span(),
+                    "Unknown modifier, expected one of `prevent`, `stop`, `once`, `capture` or `delegate`",
+                ))
+            }
+        };
+
+        modifiers.push(syn::Ident::new(method, modifier.span()));
+    }
+
+    Ok(modifiers)
+}
+
+/// Check whether the next attribute is a `class:` or `style:` directive
+fn peek_directive(input: ParseStream) -> Option<DirectiveKind> {
+    if !input.peek2(syn::token::Colon) || input.peek2(syn::token::PathSep) {
+        return None;
+    }
+
+    let ident: syn::Ident = input.fork().parse().ok()?;
+    if ident == "class" {
+        Some(DirectiveKind::Class)
+    } else if ident == "style" {
+        Some(DirectiveKind::Style)
+    } else {
+        None
+    }
+}
+
+/// Parse a CSS name like `background-color`
+fn parse_directive_name(input: ParseStream) -> syn::Result<syn::LitStr> {
+    let first = parse_path_segment(input)?.ident;
+    let mut name = first.unraw().to_string();
+
+    while input.peek(syn::token::Minus) {
+        input.parse::<syn::token::Minus>()?;
+        name.push('-');
+        name.push_str(&parse_path_segment(input)?.ident.unraw().to_string());
+    }
+
+    Ok(syn::LitStr::new(&name, first.span()))
+}
+
 fn parse_path(input: ParseStream) -> syn::Result<syn::Path> {
     let mut segments: syn::punctuated::Punctuated<syn::PathSegment, syn::token::PathSep> =
         Default::default();
@@ -615,6 +844,8 @@ fn parse_path_segment(input: ParseStream) -> syn::Result<syn::PathSegment> {
                 token_as_path_segment::<syn::token::For>(input, "for")
             } else if input.peek(syn::token::Loop) {
                 token_as_path_segment::<syn::token::Loop>(input, "loop")
+            } else if input.peek(syn::token::Ref) {
+                token_as_path_segment::<syn::token::Ref>(input, "ref")
             } else if input.peek(syn::token::Type) {
                 token_as_path_segment::<syn::token::Type>(input, "type")
             } else if input.peek(syn::token::Use) {
@@ -641,6 +872,7 @@ fn is_element_constant(nodes: &[Node], attrs: &[Attr]) -> bool {
     all_nodes_constant(nodes)
         && attrs.iter().all(|attr| match attr {
             Attr::KeyValue(key_value) => key_value_attr_constant(key_value),
+            Attr::Directive(directive) => attr_value_constant(&directive.value),
             Attr::Implicit(_) => false,
         })
 }
@@ -654,7 +886,11 @@ fn is_component_constant(children: &[Node], attrs: &ComponentAttrs) -> bool {
 }
 
 fn key_value_attr_constant(attr: &KeyValueAttr) -> bool {
-    matches!(attr.value, AttrValue::ImplicitTrue | AttrValue::Literal(_))
+    attr_value_constant(&attr.value)
+}
+
+fn attr_value_constant(value: &AttrValue) -> bool {
+    matches!(value, AttrValue::ImplicitTrue | AttrValue::Literal(_))
 }
 
 fn all_nodes_constant(nodes: &[Node]) -> bool {
@@ -734,6 +970,16 @@ mod tests {
         }
     }
 
+    fn let_stmt(stmt: syn::Stmt) -> Node {
+        let syn::Stmt::Local(local) = stmt else {
+            panic!("not a let statement");
+        };
+        Node {
+            kind: NodeKind::Let(local),
+            constant: true,
+        }
+    }
+
     #[allow(unused)]
     fn component(path: syn::Path, attrs: ComponentAttrs) -> Node {
         Node {
@@ -753,6 +999,7 @@ mod tests {
     fn attr(name: &str, value: AttrValue) -> Attr {
         Attr::KeyValue(KeyValueAttr {
             key: parse_quote!(quote::format_ident!("{}", name)),
+            modifiers: vec![],
             value,
         })
     }
@@ -762,6 +1009,7 @@ mod tests {
         let ident = quote::format_ident!("{name}");
         Attr::KeyValue(KeyValueAttr {
             key: parse_quote! { #ident },
+            modifiers: vec![],
             value,
         })
     }
@@ -902,6 +1150,132 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_event_modifiers() {
+        let node: Node = html_parse(quote! {
+            <form on:submit|prevent|once={submit} />
+        })
+        .unwrap();
+        assert_eq!(
+            html_element(
+                "form",
+                |_| vec![Attr::KeyValue(KeyValueAttr {
+                    key: parse_quote! { on::submit },
+                    modifiers: vec![parse_quote! { prevent_default }, parse_quote! { once }],
+                    value: AttrValue::Block(syn::parse_quote! { {submit} }),
+                })],
+                vec![]
+            ),
+            node,
+        );
+
+        assert!(html_parse(quote! {
+            <form on:submit|later={submit} />
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn parse_keyword_attr_path() {
+        let node: Node = html_parse(quote! {
+            <input focus:ref={input_ref} />
+        })
+        .unwrap();
+        assert_eq!(
+            html_element(
+                "input",
+                |_| vec![Attr::KeyValue(KeyValueAttr {
+                    key: parse_quote! { focus::r#ref },
+                    modifiers: vec![],
+                    value: AttrValue::Block(syn::parse_quote! { {input_ref} }),
+                })],
+                vec![]
+            ),
+            node,
+        );
+    }
+
+    #[test]
+    fn parse_element_with_directives() {
+        let node: Node = html_parse(quote! {
+            <p class:active={active} style:background-color="red" />
+        })
+        .unwrap();
+        let directive = |kind, name: &str, value| {
+            Attr::Directive(DirectiveAttr {
+                kind,
+                name: syn::LitStr::new(name, proc_macro2::Span::mixed_site()),
+                value,
+            })
+        };
+        assert_eq!(
+            html_element(
+                "p",
+                |_| vec![
+                    directive(
+                        DirectiveKind::Class,
+                        "active",
+                        AttrValue::Block(syn::parse_quote! { {active} })
+                    ),
+                    directive(
+                        DirectiveKind::Style,
+                        "background-color",
+                        AttrValue::Literal(syn::parse_quote! { "red" })
+                    ),
+                ],
+                vec![]
+            ),
+            node,
+        );
+    }
+
+    #[test]
+    fn parse_format_text() {
+        let node: Node = html_parse(quote! {
+            <p>
+                {format!("clicked {clicks} times")}
+                {"{no interpolation}"}
+                {format!("{} of {}", a, b,)}
+                {::std::format!("{label}")}
+            </p>
+        })
+        .unwrap();
+        assert_eq!(
+            html_element(
+                "p",
+                |_| vec![],
+                vec![
+                    Node {
+                        kind: NodeKind::Format(Format {
+                            format: syn::parse_quote! { "clicked {clicks} times" },
+                            args: vec![],
+                        }),
+                        constant: false,
+                    },
+                    Node {
+                        kind: NodeKind::TextExpr(syn::parse_quote! { "{no interpolation}" }),
+                        constant: false,
+                    },
+                    Node {
+                        kind: NodeKind::Format(Format {
+                            format: syn::parse_quote! { "{} of {}" },
+                            args: vec![syn::parse_quote! { a }, syn::parse_quote! { b }],
+                        }),
+                        constant: false,
+                    },
+                    Node {
+                        kind: NodeKind::Format(Format {
+                            format: syn::parse_quote! { "{label}" },
+                            args: vec![],
+                        }),
+                        constant: false,
+                    },
+                ]
+            ),
+            node,
+        );
+    }
+
     #[test]
     fn parse_if() {
         let node: Node = html_parse(quote! {
@@ -923,6 +1297,7 @@ mod tests {
                         arms: vec![
                             MatchArm {
                                 pat: syn::parse_quote! { true },
+                                guard: None,
                                 node: const_fragment(vec![
                                     const_html_element("p", |_| vec![], vec![]),
                                     const_html_element("span", |_| vec![], vec![])
@@ -930,6 +1305,7 @@ mod tests {
                             },
                             MatchArm {
                                 pat: syn::parse_quote! { false },
+                                guard: None,
                                 node: Node {
                                     kind: NodeKind::None,
                                     constant: true,
@@ -964,10 +1340,12 @@ mod tests {
                         arms: vec![
                             MatchArm {
                                 pat: syn::parse_quote! { Some(for_sure) },
+                                guard: None,
                                 node: html_element("p", |_| vec![], vec![text_var("for_sure")])
                             },
                             MatchArm {
                                 pat: syn::parse_quote! { _ },
+                                guard: None,
                                 node: Node {
                                     kind: NodeKind::None,
                                     constant: true,
@@ -982,6 +1360,150 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_else_if_chain() {
+        let node: Node = html_parse(quote! {
+            <div>
+                if a {
+                    "a"
+                } else if b {
+                    "b"
+                } else {
+                    "c"
+                }
+            </div>
+        })
+        .unwrap();
+        assert_eq!(
+            html_element(
+                "div",
+                |_| vec![],
+                vec![Node {
+                    kind: NodeKind::Match(Match {
+                        expr: syn::parse_quote! { () },
+                        arms: vec![
+                            MatchArm {
+                                pat: syn::parse_quote! { _ },
+                                guard: Some(syn::parse_quote! { a }),
+                                node: text("a"),
+                            },
+                            MatchArm {
+                                pat: syn::parse_quote! { _ },
+                                guard: Some(syn::parse_quote! { b }),
+                                node: text("b"),
+                            },
+                            MatchArm {
+                                pat: syn::parse_quote! { _ },
+                                guard: None,
+                                node: text("c"),
+                            }
+                        ],
+                    }),
+                    constant: false,
+                }]
+            ),
+            node,
+        );
+    }
+
+    #[test]
+    fn parse_match_with_guard() {
+        let node: Node = html_parse(quote! {
+            match count {
+                0 => "none"
+                n if n < 10 => "few"
+                _ => "many"
+            }
+        })
+        .unwrap();
+        assert_eq!(
+            Node {
+                kind: NodeKind::Match(Match {
+                    expr: syn::parse_quote! { count },
+                    arms: vec![
+                        MatchArm {
+                            pat: syn::parse_quote! { 0 },
+                            guard: None,
+                            node: text("none"),
+                        },
+                        MatchArm {
+                            pat: syn::parse_quote! { n },
+                            guard: Some(syn::parse_quote! { n < 10 }),
+                            node: text("few"),
+                        },
+                        MatchArm {
+                            pat: syn::parse_quote! { _ },
+                            guard: None,
+                            node: text("many"),
+                        }
+                    ],
+                }),
+                constant: false,
+            },
+            node,
+        );
+    }
+
+    #[test]
+    fn parse_let_in_children() {
+        let node: Node = html_parse(quote! {
+            <p>
+                let label = format!("{}", 42);
+                {label}
+            </p>
+        })
+        .unwrap();
+        assert_eq!(
+            html_element(
+                "p",
+                |_| vec![],
+                vec![
+                    let_stmt(syn::parse_quote! { let label = format!("{}", 42); }),
+                    text_var("label")
+                ]
+            ),
+            node,
+        );
+    }
+
+    #[test]
+    fn parse_let_in_local_block() {
+        let node: Node = html_parse(quote! {
+            match value {
+                Some(value) => {
+                    let double = value * 2;
+                    {double}
+                }
+                None => "none"
+            }
+        })
+        .unwrap();
+        assert_eq!(
+            Node {
+                kind: NodeKind::Match(Match {
+                    expr: syn::parse_quote! { value },
+                    arms: vec![
+                        MatchArm {
+                            pat: syn::parse_quote! { Some(value) },
+                            guard: None,
+                            node: fragment(vec![
+                                let_stmt(syn::parse_quote! { let double = value * 2; }),
+                                text_var("double")
+                            ]),
+                        },
+                        MatchArm {
+                            pat: syn::parse_quote! { None },
+                            guard: None,
+                            node: text("none"),
+                        }
+                    ],
+                }),
+                constant: false,
+            },
+            node,
+        );
+    }
+
     #[test]
     fn parse_for() {
         let node: Node = html_parse(quote! {
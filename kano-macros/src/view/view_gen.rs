@@ -1,14 +1,17 @@
-use proc_macro2::TokenStream;
+use std::cell::RefCell;
+
+use proc_macro2::{Span, TokenStream, TokenTree};
 use quote::{quote, quote_spanned, ToTokens};
-use syn::spanned::Spanned;
+use syn::{spanned::Spanned, visit::Visit};
 
-use crate::view::ast::{AttrValue, ComponentAttrs};
+use crate::view::ast::{AttrValue, ComponentAttrs, DirectiveAttr, DirectiveKind};
 
-use super::ast::{Attr, Element, For, Match, Node, NodeKind, View};
+use super::ast::{Attr, Element, For, Format, Match, Node, NodeKind, View};
 
 pub fn view(view: View) -> TokenStream {
     let gen = ViewGen {
         common_namespace: view.common_namespace,
+        bindings: RefCell::new(vec![]),
     };
 
     gen.node(view.root_node, Const::No)
@@ -16,6 +19,146 @@ pub fn view(view: View) -> TokenStream {
 
 struct ViewGen {
     common_namespace: Option<syn::Path>,
+    /// The variables bound by `let` children in scope of the node being generated.
+    bindings: RefCell<Vec<syn::Ident>>,
+}
+
+fn is_view(node: &Node) -> bool {
+    !matches!(node.kind, NodeKind::Let(_))
+}
+
+fn is_multi_view_fragment(node: &Node) -> bool {
+    match &node.kind {
+        NodeKind::Fragment(nodes) => nodes.iter().filter(|node| is_view(node)).count() != 1,
+        _ => false,
+    }
+}
+
+/// Collect the variables bound by a pattern.
+fn pat_idents(pat: &syn::Pat, idents: &mut Vec<syn::Ident>) {
+    match pat {
+        syn::Pat::Ident(pat_ident) => {
+            idents.push(pat_ident.ident.clone());
+            if let Some((_, subpat)) = &pat_ident.subpat {
+                pat_idents(subpat, idents);
+            }
+        }
+        syn::Pat::Tuple(tuple) => tuple.elems.iter().for_each(|pat| pat_idents(pat, idents)),
+        syn::Pat::TupleStruct(tuple) => tuple.elems.iter().for_each(|pat| pat_idents(pat, idents)),
+        syn::Pat::Struct(pat_struct) => pat_struct
+            .fields
+            .iter()
+            .for_each(|field| pat_idents(&field.pat, idents)),
+        syn::Pat::Slice(slice) => slice.elems.iter().for_each(|pat| pat_idents(pat, idents)),
+        syn::Pat::Reference(reference) => pat_idents(&reference.pat, idents),
+        syn::Pat::Paren(paren) => pat_idents(&paren.pat, idents),
+        syn::Pat::Type(pat_type) => pat_idents(&pat_type.pat, idents),
+        _ => {}
+    }
+}
+
+/// Find which of `bindings` the generated expression `tokens` captures.
+fn find_captures(tokens: TokenStream, bindings: &[syn::Ident]) -> Vec<&syn::Ident> {
+    let mut captures = Captures {
+        bindings,
+        used: vec![],
+    };
+    match syn::parse2::<syn::Expr>(tokens.clone()) {
+        Ok(expr) => captures.visit_expr(&expr),
+        Err(_) => captures.scan_tokens(tokens),
+    }
+    captures.used
+}
+
+/// Macros taking a format string, whose `{name}` arguments capture variables.
+const FORMAT_MACROS: &[&str] = &[
+    "format",
+    "format_args",
+    "print",
+    "println",
+    "eprint",
+    "eprintln",
+    "write",
+    "writeln",
+    "panic",
+];
+
+/// Collects the `let` bindings in scope that an expression uses.
+struct Captures<'a> {
+    bindings: &'a [syn::Ident],
+    used: Vec<&'a syn::Ident>,
+}
+
+impl<'a> Captures<'a> {
+    fn use_binding(&mut self, name: &str) {
+        // The innermost binding shadows the others
+        if let Some(binding) = self.bindings.iter().rev().find(|binding| *binding == name) {
+            if !self.used.contains(&binding) {
+                self.used.push(binding);
+            }
+        }
+    }
+
+    /// The variables captured by a format string, as in `format!("{label}")`.
+    fn scan_format(&mut self, format: &syn::LitStr) {
+        let format = format.value().replace("{{", "");
+        for argument in format.split('{').skip(1) {
+            if let Some(end) = argument.find(['}', ':']) {
+                self.use_binding(argument[..end].trim());
+            }
+        }
+    }
+
+    /// Tokens that are not expressions may use any binding they mention.
+    fn scan_tokens(&mut self, tokens: TokenStream) {
+        for token in tokens {
+            match token {
+                TokenTree::Ident(ident) => self.use_binding(&ident.to_string()),
+                TokenTree::Group(group) => self.scan_tokens(group.stream()),
+                TokenTree::Literal(_) | TokenTree::Punct(_) => {}
+            }
+        }
+    }
+}
+
+impl<'a, 'ast> Visit<'ast> for Captures<'a> {
+    fn visit_expr_path(&mut self, expr: &'ast syn::ExprPath) {
+        if expr.qself.is_none() {
+            if let Some(ident) = expr.path.get_ident() {
+                self.use_binding(&ident.to_string());
+            }
+        }
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let Ok(args) = mac.parse_body_with(
+            syn::punctuated::Punctuated::<syn::Expr, syn::token::Comma>::parse_terminated,
+        ) else {
+            self.scan_tokens(mac.tokens.clone());
+            return;
+        };
+
+        let is_format = mac
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| FORMAT_MACROS.contains(&segment.ident.to_string().as_str()));
+        if is_format {
+            let format = args.iter().find_map(|arg| match arg {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(format),
+                    ..
+                }) => Some(format),
+                _ => None,
+            });
+            if let Some(format) = format {
+                self.scan_format(format);
+            }
+        }
+        for arg in &args {
+            self.visit_expr(arg);
+        }
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -49,24 +192,23 @@ impl ViewGen {
                     .into_iter()
                     .map(|attr| match attr {
                         Attr::KeyValue(attr) => {
-                            let value = match attr.value {
-                                AttrValue::ImplicitTrue => quote! { true },
-                                AttrValue::Block(block) => {
-                                    let span = block.span();
-                                    quote_spanned! {span=>
-                                        #[allow(unused_braces)]
-                                        #block
-                                    }
-                                }
-                                AttrValue::Literal(lit) => {
-                                    quote! { #lit }
-                                }
-                            };
-
+                            let value = attr_value(attr.value);
                             let key = attr.key;
+                            let modifiers = attr.modifiers;
+
+                            quote_spanned! {span=>
+                                #key(#value)#(.#modifiers())*
+                            }
+                        }
+                        Attr::Directive(DirectiveAttr { kind, name, value }) => {
+                            let value = attr_value(value);
+                            let func = match kind {
+                                DirectiveKind::Class => quote_spanned! {span=> class::toggle },
+                                DirectiveKind::Style => quote_spanned! {span=> style::set },
+                            };
 
                             quote_spanned! {span=>
-                                #key(#value)
+                                #func(#name, #value)
                             }
                         }
                         Attr::Implicit(ident) => {
@@ -88,6 +230,11 @@ impl ViewGen {
                             ))
                         }
                     }
+                    Children::Scoped(block) => {
+                        quote_spanned! {span=>
+                            #path(#attrs, #block)
+                        }
+                    }
                     Children::Spread(ident) => {
                         quote_spanned! {span=>
                             #path(#attrs, #ident)
@@ -101,8 +248,31 @@ impl ViewGen {
                     view
                 }
             }
-            NodeKind::Fragment(_frag) => {
-                quote!(())
+            NodeKind::Fragment(nodes) => {
+                let (stmts, views) = self.scoped_nodes(nodes, constant);
+                match views.as_slice() {
+                    // Local bindings followed by a single view
+                    [view] => quote! {
+                        {
+                            #(#stmts)*
+                            #view
+                        }
+                    },
+                    views => quote! {
+                        {
+                            #(#stmts)*
+                            (#(#views,)*)
+                        }
+                    },
+                }
+            }
+            NodeKind::Let(local) => {
+                quote! {
+                    {
+                        #local
+                        ()
+                    }
+                }
             }
             NodeKind::Spread(ident) => {
                 quote! {
@@ -118,21 +288,27 @@ impl ViewGen {
             }
             NodeKind::TextExpr(expr) => {
                 let span = expr.span();
-                quote_spanned! {span=>
-                    ::kano::view::Reactive(move || #expr)
-                }
+                self.reactive(span, expr.into_token_stream())
+            }
+            NodeKind::Format(Format { format, args }) => {
+                let span = format.span();
+                self.reactive(
+                    span,
+                    quote_spanned! {span=>
+                        ::std::format!(#format, #(#args),*)
+                    },
+                )
             }
             NodeKind::Component(component) => {
                 let path = component.path;
                 let span = path.span();
                 match component.attrs {
-                    ComponentAttrs::Positional(positional) => {
+                    ComponentAttrs::Positional(positional) => self.reactive(
+                        span,
                         quote_spanned! {span=>
-                            ::kano::view::Reactive(move ||
-                                ::kano::view::Func(#path, (#(#positional),*,))
-                            )
-                        }
-                    }
+                            ::kano::view::Func(#path, (#(#positional),*,))
+                        },
+                    ),
                     ComponentAttrs::KeyValue(_) => {
                         quote_spanned! {span=>
                             ::kano::view::Func(#path, ())
@@ -142,28 +318,28 @@ impl ViewGen {
             }
             NodeKind::Match(Match { expr, arms }) => {
                 let span = expr.span();
+                let arm_count = arms.len();
+
                 let arms = arms.into_iter().enumerate().map(|(index, arm)| {
                     let pat = arm.pat;
                     let span = pat.span();
-                    let view = self.node(arm.node, constant);
-                    if index == 0 {
-                        quote_spanned! {span=>
-                            #pat => ::kano::view::Either::Left(#view),
-                        }
-                    } else {
-                        quote_spanned! {span=>
-                            #pat => ::kano::view::Either::Right(#view),
-                        }
+                    let guard = arm.guard.map(|guard| quote! { if #guard });
+                    let view = sum_variant(index, arm_count, self.node(arm.node, constant));
+
+                    quote_spanned! {span=>
+                        #pat #guard => #view,
                     }
                 });
 
-                quote_spanned! {span=>
-                    ::kano::view::Reactive(move || {
+                let arms: Vec<_> = arms.collect();
+                self.reactive(
+                    span,
+                    quote_spanned! {span=>
                         match #expr {
                             #(#arms)*
                         }
-                    })
-                }
+                    },
+                )
             }
             NodeKind::For(For {
                 for_token,
@@ -174,18 +350,34 @@ impl ViewGen {
             }) => {
                 let span = for_token.span;
                 let child = self.node(*repeating_node, constant);
+                let (outer, inner) = self.clone_bindings(&child);
 
                 quote_spanned! {span=>
-                    ::kano::view::seq_map(#expression, move |#pat| {
-                        #child
-                    })
+                    {
+                        #outer
+                        ::kano::view::seq_map(#expression, move |#pat| {
+                            #inner
+                            #child
+                        })
+                    }
                 }
             }
         }
     }
 
     fn gen_children(&self, nodes: Vec<Node>, constant: Const) -> Children {
-        if nodes.len() == 1 {
+        if nodes
+            .iter()
+            .any(|node| matches!(node.kind, NodeKind::Let(_)) || is_multi_view_fragment(node))
+        {
+            let (stmts, views) = self.scoped_nodes(nodes, constant);
+            Children::Scoped(quote! {
+                {
+                    #(#stmts)*
+                    (#(#views,)*)
+                }
+            })
+        } else if nodes.len() == 1 {
             let node = nodes.into_iter().next().unwrap();
             match node.kind {
                 NodeKind::Spread(ident) => Children::Spread(ident),
@@ -201,6 +393,96 @@ impl ViewGen {
         }
     }
 
+    /// A view re-evaluating `body` whenever its dependencies change.
+    fn reactive(&self, span: Span, body: TokenStream) -> TokenStream {
+        let (outer, inner) = self.clone_bindings(&body);
+
+        quote_spanned! {span=>
+            {
+                #outer
+                ::kano::view::Reactive(move || {
+                    #inner
+                    #body
+                })
+            }
+        }
+    }
+
+    /// Statements cloning the `let` bindings used in `body` before and inside a closure,
+    /// so that each closure owns its copy and can be called repeatedly.
+    fn clone_bindings(&self, body: &TokenStream) -> (TokenStream, TokenStream) {
+        let bindings = self.bindings.borrow();
+        let used = find_captures(body.clone(), &bindings);
+
+        let clones = quote! {
+            #(
+                #[allow(unused_variables)]
+                let #used = ::core::clone::Clone::clone(&#used);
+            )*
+        };
+        (clones.clone(), clones)
+    }
+
+    /// Generate a list of nodes containing `let` statements into a sequence of statements.
+    ///
+    /// Each view is bound to its own variable in order,
+    /// so that a `let` binding is only in scope for the siblings following it.
+    fn scoped_nodes(
+        &self,
+        nodes: Vec<Node>,
+        constant: Const,
+    ) -> (Vec<TokenStream>, Vec<syn::Ident>) {
+        let mut stmts = vec![];
+        let mut views = vec![];
+        let outer_bindings = self.bindings.borrow().len();
+
+        for node in nodes {
+            match node {
+                Node {
+                    kind: NodeKind::Let(local),
+                    ..
+                } => {
+                    pat_idents(&local.pat, &mut self.bindings.borrow_mut());
+                    stmts.push(local.into_token_stream());
+                }
+                Node {
+                    kind: NodeKind::Fragment(nodes),
+                    ..
+                } if nodes.iter().filter(|node| is_view(node)).count() != 1 => {
+                    // A fragment of several views is spliced into the siblings,
+                    // with its own `let` bindings only in scope within it
+                    let (inner_stmts, inner_views) = self.scoped_nodes(nodes, constant);
+                    let idents: Vec<_> = (0..inner_views.len())
+                        .map(|index| {
+                            quote::format_ident!(
+                                "child{}",
+                                views.len() + index,
+                                span = Span::mixed_site()
+                            )
+                        })
+                        .collect();
+                    stmts.push(quote! {
+                        let (#(#idents,)*) = {
+                            #(#inner_stmts)*
+                            (#(#inner_views,)*)
+                        };
+                    });
+                    views.extend(idents);
+                }
+                node => {
+                    let ident =
+                        quote::format_ident!("child{}", views.len(), span = Span::mixed_site());
+                    let view = self.node(node, constant);
+                    stmts.push(quote! { let #ident = #view; });
+                    views.push(ident);
+                }
+            }
+        }
+
+        self.bindings.borrow_mut().truncate(outer_bindings);
+        (stmts, views)
+    }
+
     fn element_path(&self, path: &syn::Path) -> TokenStream {
         let span = path.span();
 
@@ -218,7 +500,56 @@ impl ViewGen {
     }
 }
 
+fn attr_value(value: AttrValue) -> TokenStream {
+    match value {
+        AttrValue::ImplicitTrue => quote! { true },
+        AttrValue::Block(block) => {
+            let span = block.span();
+            quote_spanned! {span=>
+                #[allow(unused_braces)]
+                #block
+            }
+        }
+        AttrValue::Literal(lit) => {
+            quote! { #lit }
+        }
+    }
+}
+
+/// The largest `OneOfN` view available in `kano::view`.
+const MAX_MATCH_ARMS: usize = 12;
+
+/// Wrap the view of match arm `index` in the sum view variant representing that arm,
+/// so that every arm gets its own state type.
+///
+/// With more arms than the largest sum view, the last variant nests a sum view of the remaining arms.
+fn sum_variant(index: usize, arm_count: usize, view: TokenStream) -> TokenStream {
+    const LAST: usize = MAX_MATCH_ARMS - 1;
+
+    match (arm_count, index) {
+        (1, _) => view,
+        (2, 0) => quote! { ::kano::view::Either::Left(#view) },
+        (2, _) => quote! { ::kano::view::Either::Right(#view) },
+        (count, index) if count > MAX_MATCH_ARMS => {
+            let sum = quote::format_ident!("OneOf{MAX_MATCH_ARMS}");
+            let variant = quote::format_ident!("V{}", index.min(LAST));
+            let view = if index >= LAST {
+                sum_variant(index - LAST, count - LAST, view)
+            } else {
+                view
+            };
+            quote! { ::kano::view::#sum::#variant(#view) }
+        }
+        _ => {
+            let sum = quote::format_ident!("OneOf{arm_count}");
+            let variant = quote::format_ident!("V{index}");
+            quote! { ::kano::view::#sum::#variant(#view) }
+        }
+    }
+}
+
 enum Children {
     Listed(Vec<TokenStream>),
+    Scoped(TokenStream),
     Spread(syn::Ident),
 }
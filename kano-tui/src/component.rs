@@ -1,76 +1,213 @@
 use std::rc::Rc;
 
 use kano::{
-    attr::{Click, On},
-    vdom::vnode::{VNode, VNodeRef},
+    attr::{Bind, Event, FocusAttr, On},
+    event::ScrollEvent,
+    focus::Registration,
+    vdom::vnode::VNodeRef,
     Children,
 };
 use ratatui::{
+    layout::Rect,
     style::{Color, Modifier},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, Padding, Paragraph, Wrap},
+    widgets::{
+        Block, BorderType, Borders, Padding, Paragraph, Scrollbar, ScrollbarOrientation,
+        ScrollbarState, Wrap,
+    },
     Frame,
 };
 
 use crate::{
     node_data::{NodeData, NodeKind},
     tui_cursor::TuiCursor,
+    tui_focus::{self, FocusRequest},
+    tui_scroll::Viewport,
     tui_state::TuiState,
+    tui_svg,
+    tui_text_input::{self, TextCursor},
     Tml, Tui,
 };
 
 #[derive(Clone)]
 pub struct Component<C> {
     pub data: Rc<ComponentData>,
-    pub on_click: Option<On<Click>>,
+    pub handlers: Vec<On<Event>>,
+    pub bind: Option<Bind>,
+    pub focus: Vec<FocusAttr>,
     pub children: C,
 }
 
 impl<C: Children<Tui, Tml>> kano::View<Tui, Tml> for Component<C> {
-    type ConstState = C::ConstState;
-    type DiffState = (Rc<ComponentData>, C::DiffState);
+    type ConstState = (Option<Registration>, C::ConstState);
+    type DiffState = (Rc<ComponentData>, Option<Registration>, C::DiffState);
 
     fn init_const(self, cursor: &mut TuiCursor) -> Self::ConstState {
         cursor.set_component(self.data.clone());
-        cursor.set_on_click(self.on_click.clone());
+        cursor.set_handlers(self.handlers.clone());
+        cursor.set_bind(self.bind);
+        let registration = init_focus(self.focus, cursor);
 
-        self.children.init_const(cursor)
+        (registration, self.children.init_const(cursor))
     }
 
     fn init_diff(self, cursor: &mut TuiCursor) -> Self::DiffState {
         cursor.set_component(self.data.clone());
 
-        cursor.set_on_click(self.on_click.clone());
+        cursor.set_handlers(self.handlers.clone());
+        cursor.set_bind(self.bind);
+        let registration = init_focus(self.focus, cursor);
 
         let children_state = self.children.init_diff(cursor);
 
-        (self.data, children_state)
+        (self.data, registration, children_state)
     }
 
     fn diff(self, state: &mut Self::DiffState, cursor: &mut TuiCursor) {
-        cursor.set_on_click(self.on_click);
-        self.children.diff(&mut state.1, cursor);
+        if !Rc::ptr_eq(&state.0, &self.data) {
+            cursor.update_component(self.data.clone());
+            state.0 = self.data;
+        }
+        cursor.set_handlers(self.handlers);
+        cursor.set_bind(self.bind);
+
+        if state.1.as_ref().map(Registration::focus_ref) != tui_focus::focus_ref(&self.focus) {
+            state.1 = tui_focus::register(cursor.current_node().id(), &self.focus);
+        }
+        cursor.set_focus(self.focus);
+
+        self.children.diff(&mut state.2, cursor);
     }
 }
 
+/// Attach focus properties to the current node.
+fn init_focus(focus: Vec<FocusAttr>, cursor: &mut TuiCursor) -> Option<Registration> {
+    let node_id = cursor.current_node().id();
+    if focus.contains(&FocusAttr::Autofocus(true)) {
+        tui_focus::request(FocusRequest::Focus(node_id));
+    }
+
+    let registration = tui_focus::register(node_id, &focus);
+    cursor.set_focus(focus);
+    registration
+}
+
 #[derive(Clone, Debug)]
 pub struct ComponentData {
     pub layout: Layout,
     pub style: Style,
 }
 
+/// How a component is laid out.
+///
+/// Boxes divide the area of their parent box between them.
+/// The other layouts are flowing content, rendered as text within the area left to them.
 #[derive(Clone, Debug)]
 pub enum Layout {
     Block,
     Paragraph,
     Inline,
-    Svg,
+    Box(BoxLayout),
+    TextInput(TextInputLayout),
+}
+
+/// A box that lays out its children in a row or a column.
+///
+/// Runs of flowing content among the children take [Size::default] each.
+/// Flowing content that contains boxes stacks its children in a column, like a box without decoration.
+#[derive(Clone, Default, Debug)]
+pub struct BoxLayout {
+    pub direction: BoxDirection,
+    /// The size of the box along the direction of its parent.
+    pub size: Size,
+    pub padding: Padding,
+    pub border: Option<BorderType>,
+    /// A title shown in the top border.
+    pub title: Option<String>,
+    /// Whether the content scrolls vertically when it doesn't fit, with a scrollbar.
+    ///
+    /// The content of a scrolling box flows, boxes within it are not laid out.
+    pub scroll: bool,
+}
+
+/// An input for editing the text of a [Bind::Value], shown in place of the children of the component.
+#[derive(Clone, Default, Debug)]
+pub struct TextInputLayout {
+    /// Text shown while the value is empty.
+    pub placeholder: Option<String>,
+    /// Whether to mask the value.
+    pub password: bool,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum BoxDirection {
+    Row,
+    #[default]
+    Column,
+}
+
+/// The size of a box along the direction of its parent.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Size {
+    /// A fixed number of cells.
+    Fixed(u16),
+    /// A share, by weight, of the cells that fixed sizes leave over.
+    Flex(u16),
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Self::Flex(1)
+    }
+}
+
+/// Split `length` cells between `sizes`.
+///
+/// Fixed sizes are served first, in order, and flexible sizes share what is left by weight.
+pub(crate) fn split(length: u16, sizes: &[Size]) -> Vec<u16> {
+    let mut remaining = length;
+    let mut lengths: Vec<u16> = sizes
+        .iter()
+        .map(|size| match size {
+            Size::Fixed(fixed) => {
+                let fixed = (*fixed).min(remaining);
+                remaining -= fixed;
+                fixed
+            }
+            Size::Flex(_) => 0,
+        })
+        .collect();
+
+    let total_weight: u32 = sizes
+        .iter()
+        .map(|size| match size {
+            Size::Flex(weight) => *weight as u32,
+            Size::Fixed(_) => 0,
+        })
+        .sum();
+    if total_weight == 0 {
+        return lengths;
+    }
+
+    // Round cumulatively, so that the shares add up to exactly what is left
+    let (mut weight_so_far, mut handed_out) = (0, 0);
+    for (length, size) in lengths.iter_mut().zip(sizes) {
+        if let Size::Flex(weight) = size {
+            weight_so_far += *weight as u32;
+            let end = remaining as u32 * weight_so_far / total_weight;
+            *length = (end - handed_out) as u16;
+            handed_out = end;
+        }
+    }
+
+    lengths
 }
 
 #[derive(Clone, Copy)]
 pub enum StyleState {
     Normal,
     Focused,
+    Hovered,
 }
 
 #[derive(Clone, Default, Debug)]
@@ -86,6 +223,7 @@ pub struct Style {
 pub struct StateKeyed<T> {
     pub normal: T,
     pub focused: T,
+    pub hovered: T,
 }
 
 impl<T> StateKeyed<T> {
@@ -95,7 +233,8 @@ impl<T> StateKeyed<T> {
     {
         Self {
             normal: value.clone(),
-            focused: value,
+            focused: value.clone(),
+            hovered: value,
         }
     }
 
@@ -103,6 +242,7 @@ impl<T> StateKeyed<T> {
         match state {
             StyleState::Normal => &self.normal,
             StyleState::Focused => &self.focused,
+            StyleState::Hovered => &self.hovered,
         }
     }
 }
@@ -113,32 +253,324 @@ impl ComponentData {
         node: VNodeRef<NodeData>,
         tui_state: &mut TuiState,
         frame: &mut Frame,
-        area: ratatui::prelude::Rect,
+        area: Rect,
+    ) {
+        let block = Block::default()
+            .title(tui_state.title.clone())
+            .borders(Borders::ALL)
+            .border_set(ratatui::symbols::border::DOUBLE)
+            .padding(Padding::uniform(1));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        Renderer { tui_state, frame }.render_items(
+            vec![node],
+            BoxDirection::Column,
+            inner,
+            Default::default(),
+            StyleState::Normal,
+        );
+    }
+}
+
+/// A part of a box: a nested box, or a run of flowing content.
+enum Item {
+    Box(VNodeRef<NodeData>, Size),
+    Flow(Vec<VNodeRef<NodeData>>),
+}
+
+fn items(nodes: Vec<VNodeRef<NodeData>>) -> Vec<Item> {
+    let mut items = vec![];
+    for node in nodes {
+        match box_size(&node) {
+            Some(size) => items.push(Item::Box(node, size)),
+            None => match items.last_mut() {
+                Some(Item::Flow(run)) => run.push(node),
+                _ => items.push(Item::Flow(vec![node])),
+            },
+        }
+    }
+    items
+}
+
+/// The size of a node that is laid out as a box, or `None` for flowing content.
+fn box_size(node: &VNodeRef<NodeData>) -> Option<Size> {
+    match &node.0.borrow().data.kind {
+        NodeKind::Component(data) => match &data.layout {
+            Layout::Box(box_layout) => Some(box_layout.size),
+            Layout::TextInput(_) => None,
+            Layout::Block | Layout::Paragraph | Layout::Inline => {
+                contains_box(node).then(Size::default)
+            }
+        },
+        _ => None,
+    }
+}
+
+fn contains_box(node: &VNodeRef<NodeData>) -> bool {
+    all_children(node.clone())
+        .iter()
+        .any(|child| match &child.0.borrow().data.kind {
+            NodeKind::Component(data) => match data.layout {
+                Layout::Box(_) => true,
+                Layout::TextInput(_) => false,
+                _ => contains_box(child),
+            },
+            _ => false,
+        })
+}
+
+struct Renderer<'r, 'f> {
+    tui_state: &'r mut TuiState,
+    frame: &'r mut Frame<'f>,
+}
+
+impl<'r, 'f> Renderer<'r, 'f> {
+    fn render_items(
+        &mut self,
+        nodes: Vec<VNodeRef<NodeData>>,
+        direction: BoxDirection,
+        area: Rect,
+        tui_style: ratatui::style::Style,
+        style_state: StyleState,
+    ) {
+        let items = items(nodes);
+        let sizes: Vec<Size> = items
+            .iter()
+            .map(|item| match item {
+                Item::Box(_, size) => *size,
+                Item::Flow(_) => Size::default(),
+            })
+            .collect();
+        let length = match direction {
+            BoxDirection::Row => area.width,
+            BoxDirection::Column => area.height,
+        };
+
+        let mut offset = 0;
+        for (item, length) in items.into_iter().zip(split(length, &sizes)) {
+            let rect = match direction {
+                BoxDirection::Row => Rect::new(area.x + offset, area.y, length, area.height),
+                BoxDirection::Column => Rect::new(area.x, area.y + offset, area.width, length),
+            };
+            offset += length;
+
+            match item {
+                Item::Box(node, _) => self.render_box(node, rect, tui_style, style_state),
+                Item::Flow(nodes) => self.render_flow(nodes, rect, tui_style, style_state),
+            }
+        }
+    }
+
+    fn render_box(
+        &mut self,
+        node: VNodeRef<NodeData>,
+        area: Rect,
+        tui_style: ratatui::style::Style,
+        outer_state: StyleState,
+    ) {
+        let node_borrow = node.0.borrow();
+        let NodeKind::Component(data) = &node_borrow.data.kind else {
+            return;
+        };
+
+        let visit = enter(self.tui_state, &node, outer_state);
+        let mut style = tui_style;
+        apply_style(&mut style, &data.style, visit.style_state);
+
+        let mut block = Block::default().style(style);
+        let mut direction = BoxDirection::Column;
+        let mut scroll = false;
+        if let Layout::Box(box_layout) = &data.layout {
+            direction = box_layout.direction;
+            scroll = box_layout.scroll;
+            block = block.padding(box_layout.padding);
+            if let Some(border_type) = box_layout.border {
+                block = block.borders(Borders::ALL).border_type(border_type);
+            }
+            if let Some(title) = &box_layout.title {
+                block = block.title(title.clone());
+            }
+        }
+        let inner = block.inner(area);
+        self.frame.render_widget(block, area);
+
+        let children = all_children(node.clone());
+        if scroll {
+            self.render_scroll(node.id(), children, inner, style, visit.style_state);
+        } else {
+            self.render_items(children, direction, inner, style, visit.style_state);
+        }
+        leave(self.tui_state, visit, area);
+    }
+
+    fn render_flow(
+        &mut self,
+        nodes: Vec<VNodeRef<NodeData>>,
+        area: Rect,
+        tui_style: ratatui::style::Style,
+        style_state: StyleState,
+    ) {
+        let lines = self.collect_flow(nodes, area, tui_style, style_state);
+        self.frame.render_widget(
+            Paragraph::new(Text::from(lines)).wrap(Wrap { trim: true }),
+            area,
+        );
+    }
+
+    /// Render flowing content that scrolls vertically within `area`, with a scrollbar on the right.
+    fn render_scroll(
+        &mut self,
+        node_id: u64,
+        nodes: Vec<VNodeRef<NodeData>>,
+        area: Rect,
+        tui_style: ratatui::style::Style,
+        style_state: StyleState,
     ) {
+        let content_area = Rect {
+            width: area.width.saturating_sub(1),
+            ..area
+        };
+        let first_rect = self.tui_state.node_rects.len();
+        let first_focusable = self.tui_state.focusable_counter;
+
+        let lines = self.collect_flow(nodes, content_area, tui_style, style_state);
+        let paragraph = Paragraph::new(Text::from(lines)).wrap(Wrap { trim: true });
+        let content_height = paragraph.line_count(content_area.width) as u16;
+        let viewport = Viewport {
+            height: area.height,
+            max_top: content_height.saturating_sub(area.height),
+        };
+
+        let mut top = self
+            .tui_state
+            .scroll_offsets
+            .get(&node_id)
+            .map_or(0, |offset| offset.top as u16);
+
+        // Reveal the focused node when focus has moved to it
+        let focusables = first_focusable..self.tui_state.focusable_counter;
+        if let Some(focused) = self
+            .tui_state
+            .currently_focused
+            .filter(|focused| focusables.contains(focused))
+        {
+            if self.tui_state.revealed_focus != Some(focused) {
+                let rect = self.tui_state.focus_rects[focused];
+                let row = rect.y.saturating_sub(area.y);
+                top = top
+                    .max((row + rect.height).saturating_sub(area.height))
+                    .min(row);
+            }
+        }
+
+        let top = top.min(viewport.max_top);
+        self.tui_state.scroll_offsets.insert(
+            node_id,
+            ScrollEvent {
+                left: 0,
+                top: top as i32,
+            },
+        );
+        self.tui_state.viewports.insert(node_id, viewport);
+
+        // Move the areas of the content to where they are shown
+        let scrolled = |rect: Rect| {
+            let y = rect.y.saturating_sub(top).max(area.y);
+            let bottom = rect.bottom().saturating_sub(top);
+            Rect::new(rect.x, y, rect.width, bottom.saturating_sub(y)).intersection(area)
+        };
+        for (_, rect) in &mut self.tui_state.node_rects[first_rect..] {
+            *rect = scrolled(*rect);
+        }
+        for rect in &mut self.tui_state.focus_rects[focusables] {
+            *rect = scrolled(*rect);
+        }
+
+        self.frame
+            .render_widget(paragraph.scroll((top, 0)), content_area);
+
+        if viewport.max_top > 0 {
+            let position = top as usize * content_height as usize / viewport.max_top as usize;
+            self.frame.render_stateful_widget(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight),
+                area,
+                &mut ScrollbarState::new(content_height as usize)
+                    .viewport_content_length(area.height as usize)
+                    .position(position),
+            );
+        }
+    }
+
+    fn collect_flow(
+        &mut self,
+        nodes: Vec<VNodeRef<NodeData>>,
+        area: Rect,
+        tui_style: ratatui::style::Style,
+        style_state: StyleState,
+    ) -> Vec<Line<'static>> {
         let mut collector = Collector {
             spans: vec![],
             lines: vec![],
-            tui_state,
-            style_state: StyleState::Normal,
+            tui_state: self.tui_state,
+            style_state,
+            inner: area,
         };
-        collector.collect_lines(node, Default::default());
+        for node in nodes {
+            collector.collect_lines(node, tui_style);
+        }
 
         if !collector.spans.is_empty() {
             collector.lines.push(Line::from(collector.spans));
         }
+        collector.lines
+    }
+}
 
-        frame.render_widget(
-            Paragraph::new(Text::from(collector.lines))
-                .wrap(Wrap { trim: true })
-                .block(
-                    Block::default()
-                        .title("Kano TUI. Press q to quit.")
-                        .borders(Borders::ALL)
-                        .border_set(ratatui::symbols::border::DOUBLE)
-                        .padding(Padding::uniform(1)),
-                ),
-            area,
-        );
+/// Bookkeeping of a component node while it is rendered.
+struct Visit {
+    rect_index: usize,
+    focus_index: Option<usize>,
+    style_state: StyleState,
+}
+
+/// Start rendering a component node, within content in `outer_state`.
+fn enter(tui_state: &mut TuiState, node: &VNodeRef<NodeData>, outer_state: StyleState) -> Visit {
+    let node_borrow = node.0.borrow();
+    let mut visit = Visit {
+        rect_index: tui_state.node_rects.len(),
+        focus_index: None,
+        style_state: outer_state,
+    };
+    tui_state.node_rects.push((node.clone(), Rect::default()));
+
+    if node_borrow.data.is_focusable() {
+        visit.focus_index = Some(tui_state.focusable_counter);
+        tui_state.focus_rects.push(Rect::default());
+
+        if Some(tui_state.focusable_counter) == tui_state.currently_focused {
+            tui_state.focused_node = Some(node.clone());
+            tui_state.focused_bind = node_borrow.data.bind;
+            visit.style_state = StyleState::Focused;
+        }
+
+        tui_state.focusable_counter += 1;
+    }
+
+    if let StyleState::Normal = visit.style_state {
+        if tui_state.is_hovered(node) {
+            visit.style_state = StyleState::Hovered;
+        }
+    }
+
+    visit
+}
+
+/// Finish rendering a component node, that ended up covering `rect`.
+fn leave(tui_state: &mut TuiState, visit: Visit, rect: Rect) {
+    tui_state.node_rects[visit.rect_index].1 = rect;
+    if let Some(index) = visit.focus_index {
+        tui_state.focus_rects[index] = rect;
     }
 }
 
@@ -159,9 +591,30 @@ struct Collector<'t, 's> {
     lines: Vec<Line<'t>>,
     tui_state: &'s mut TuiState,
     style_state: StyleState,
+    /// The area the lines are rendered within.
+    inner: Rect,
 }
 
 impl<'t, 's> Collector<'t, 's> {
+    /// The current line and column, ignoring wrapping.
+    fn position(&self) -> (usize, usize) {
+        let column = self.spans.iter().map(Span::width).sum();
+        (self.lines.len(), column)
+    }
+
+    /// The approximate area covered between two positions.
+    fn rect_between(&self, (line, column): (usize, usize), end: (usize, usize)) -> Rect {
+        let y = self.inner.y.saturating_add(line as u16);
+        if end.0 == line {
+            let x = self.inner.x.saturating_add(column as u16);
+            Rect::new(x, y, end.1.saturating_sub(column).max(1) as u16, 1)
+        } else {
+            // A partial last line counts as a whole row
+            let height = end.0 - line + usize::from(end.1 > 0);
+            Rect::new(self.inner.x, y, self.inner.width, height as u16)
+        }
+    }
+
     fn collect_lines(&mut self, node: VNodeRef<NodeData>, tui_style: ratatui::style::Style) {
         let node_borrow = node.0.borrow();
 
@@ -170,30 +623,29 @@ impl<'t, 's> Collector<'t, 's> {
             NodeKind::Text(text) => {
                 self.spans.push(Span::styled(text.clone(), tui_style));
             }
+            NodeKind::Svg(_) => {
+                // An image is a block of its own lines
+                if !self.spans.is_empty() {
+                    self.lines.push(Line::from(std::mem::take(&mut self.spans)));
+                }
+                let marker = self.tui_state.svg_marker;
+                self.lines
+                    .extend(tui_svg::rasterize(&node, self.inner.width, marker));
+            }
             NodeKind::Component(data) => {
                 match &data.layout {
-                    Layout::Block | Layout::Paragraph => {
+                    // Flowing content never contains boxes, see `box_size`
+                    Layout::Block | Layout::Paragraph | Layout::Box(_) => {
                         if !self.spans.is_empty() {
                             self.lines.push(Line::from(std::mem::take(&mut self.spans)));
                         }
                     }
-                    Layout::Inline => {}
-                    Layout::Svg => {
-                        self.spans.push(Span::styled("🖼️", tui_style));
-                        return;
-                    }
+                    Layout::Inline | Layout::TextInput(_) => {}
                 }
 
-                let mut unfocus = false;
-                if let Some(click_handler) = find_click_handler(&node_borrow) {
-                    if self.tui_state.focusable_counter == self.tui_state.currently_focused {
-                        self.tui_state.focused_event_handler = Some(click_handler);
-                        self.style_state = StyleState::Focused;
-                        unfocus = true;
-                    }
-
-                    self.tui_state.focusable_counter += 1;
-                }
+                let start = self.position();
+                let visit = enter(self.tui_state, &node, self.style_state);
+                let outer_state = std::mem::replace(&mut self.style_state, visit.style_state);
 
                 if let Some((prefix, style)) = &data.style.prefix {
                     let mut prefix_style = tui_style;
@@ -204,11 +656,15 @@ impl<'t, 's> Collector<'t, 's> {
                 let mut sub_style = tui_style;
                 apply_style(&mut sub_style, &data.style, self.style_state);
 
-                let mut next_child = node.first_child();
+                if let Layout::TextInput(layout) = &data.layout {
+                    self.collect_text_input(&node, layout, visit.focus_index, sub_style);
+                } else {
+                    let mut next_child = node.first_child();
 
-                while let Some(child) = next_child {
-                    self.collect_lines(child.clone(), sub_style);
-                    next_child = child.next_sibling();
+                    while let Some(child) = next_child {
+                        self.collect_lines(child.clone(), sub_style);
+                        next_child = child.next_sibling();
+                    }
                 }
 
                 if let Some((postfix, style)) = &data.style.postfix {
@@ -217,12 +673,40 @@ impl<'t, 's> Collector<'t, 's> {
                     self.spans.push(Span::styled(*postfix, postfix_style));
                 }
 
-                if unfocus {
-                    self.style_state = StyleState::Normal;
-                }
+                self.style_state = outer_state;
+
+                let rect = self.rect_between(start, self.position());
+                leave(self.tui_state, visit, rect);
             }
         }
     }
+
+    fn collect_text_input(
+        &mut self,
+        node: &VNodeRef<NodeData>,
+        layout: &TextInputLayout,
+        focus_index: Option<usize>,
+        tui_style: ratatui::style::Style,
+    ) {
+        let value = match node.0.borrow().data.bind {
+            Some(Bind::Value(state)) => state.get(),
+            _ => String::new(),
+        };
+
+        // The cursor is shown while the input is focused
+        let cursor = focus_index
+            .filter(|index| Some(*index) == self.tui_state.currently_focused)
+            .map(|_| {
+                *self
+                    .tui_state
+                    .text_cursors
+                    .entry(node.id())
+                    .or_insert_with(|| TextCursor::at_end(&value))
+            });
+
+        self.spans
+            .extend(tui_text_input::spans(&value, layout, cursor, tui_style));
+    }
 }
 
 fn apply_style(tui_style: &mut ratatui::style::Style, style: &Style, state: StyleState) {
@@ -237,6 +721,119 @@ fn apply_style(tui_style: &mut ratatui::style::Style, style: &Style, state: Styl
     }
 }
 
-fn find_click_handler(node: &VNode<NodeData>) -> Option<On<Click>> {
-    node.data.on_click.clone()
+#[cfg(test)]
+mod tests {
+    use kano::{markup::Cursor, property::on};
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::*;
+
+    fn render(root: &VNodeRef<NodeData>, tui_state: &mut TuiState, width: u16, height: u16) {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|frame| {
+                crate::render_node(root.first_child().unwrap(), tui_state, frame, frame.size())
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn split_serves_fixed_sizes_first() {
+        assert_eq!(
+            vec![3, 3, 4],
+            split(10, &[Size::Fixed(3), Size::Flex(1), Size::Flex(1)])
+        );
+        assert_eq!(vec![6, 3], split(9, &[Size::Flex(2), Size::Flex(1)]));
+        assert_eq!(
+            vec![4, 1, 0],
+            split(5, &[Size::Fixed(4), Size::Fixed(4), Size::Flex(1)])
+        );
+    }
+
+    #[test]
+    fn boxes_divide_their_area() {
+        let data = |direction, size| {
+            Rc::new(ComponentData {
+                layout: Layout::Box(BoxLayout {
+                    direction,
+                    size,
+                    border: Some(BorderType::Plain),
+                    ..Default::default()
+                }),
+                style: Style::default(),
+            })
+        };
+
+        // row { sidebar, main }
+        let (mut cursor, root) = TuiCursor::new_root();
+        cursor.set_component(data(BoxDirection::Row, Size::default()));
+        cursor.enter_children();
+        cursor.set_component(data(BoxDirection::Column, Size::Fixed(10)));
+        cursor.set_component(data(BoxDirection::Column, Size::Flex(1)));
+
+        let mut tui_state = TuiState::default();
+        render(&root, &mut tui_state, 40, 12);
+
+        let rects: Vec<Rect> = tui_state.node_rects.iter().map(|(_, rect)| *rect).collect();
+        // The frame takes a border and a padding of one cell on each side
+        assert_eq!(
+            vec![
+                Rect::new(2, 2, 36, 8),
+                Rect::new(3, 3, 10, 6),
+                Rect::new(13, 3, 24, 6),
+            ],
+            rects
+        );
+    }
+
+    #[test]
+    fn scrolling_box_reveals_the_focused_node() {
+        let paragraph = Rc::new(ComponentData {
+            layout: Layout::Paragraph,
+            style: Style::default(),
+        });
+
+        // scroll { paragraph * 20 }, where the last paragraph is focusable
+        let (mut cursor, root) = TuiCursor::new_root();
+        cursor.set_component(Rc::new(ComponentData {
+            layout: Layout::Box(BoxLayout {
+                scroll: true,
+                ..Default::default()
+            }),
+            style: Style::default(),
+        }));
+        cursor.enter_children();
+        for line in 0..20 {
+            cursor.set_component(paragraph.clone());
+            if line == 19 {
+                cursor.set_handlers(vec![on::click(|_| {}).into()]);
+            }
+            cursor.enter_children();
+            cursor.text(&format!("line {line}"));
+            cursor.exit_children();
+        }
+
+        let scroll_id = root.first_child().unwrap().id();
+        let mut tui_state = TuiState {
+            currently_focused: Some(0),
+            ..Default::default()
+        };
+        // The frame leaves 6 of the 10 rows to the content
+        render(&root, &mut tui_state, 20, 10);
+
+        assert_eq!(14, tui_state.scroll_offsets[&scroll_id].top);
+        assert_eq!(14, tui_state.viewports[&scroll_id].max_top);
+        assert_eq!(Rect::new(2, 7, 7, 1), tui_state.focus_rects[0]);
+
+        // The offset is kept while the box is rendered, and forgotten after that
+        tui_state.on_post_frame();
+        assert!(tui_state.scroll_offsets.contains_key(&scroll_id));
+
+        let (mut cursor, other_root) = TuiCursor::new_root();
+        cursor.set_component(paragraph);
+        tui_state.on_pre_frame();
+        render(&other_root, &mut tui_state, 20, 10);
+        tui_state.on_post_frame();
+        assert!(tui_state.scroll_offsets.is_empty());
+    }
 }
@@ -0,0 +1,249 @@
+//! A headless driver for testing TUI apps without a terminal.
+//!
+//! The app renders into a [TestBackend] buffer, and tests inject terminal events
+//! and compare the rendered text with snapshots:
+//!
+//! ```ignore
+//! let mut tui = HeadlessTui::new(40, 10, app);
+//! tui.press(KeyCode::Tab);
+//! assert_eq!(expected, tui.text());
+//! ```
+
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use kano::{view::Reactive, Init, View};
+use ratatui::{backend::TestBackend, buffer::Buffer, layout::Rect, text::Span, Terminal};
+
+use crate::{set_viewport, tui_app::TuiApp, tui_wake, Tml, Tui};
+
+/// Runs an app on the TUI platform, rendering into a buffer instead of a terminal.
+pub struct HeadlessTui {
+    app: TuiApp,
+    terminal: Terminal<TestBackend>,
+    quit: bool,
+}
+
+impl HeadlessTui {
+    /// Render the app returned by `func` into a buffer of `width` by `height` cells.
+    pub fn new<V>(width: u16, height: u16, func: impl (Fn() -> V) + 'static) -> Self
+    where
+        V: View<Tui, Tml> + 'static,
+    {
+        Self::with_init(kano::init(), width, height, func)
+    }
+
+    /// Like [Self::new], with the platform initialized and configured by the caller.
+    pub fn with_init<V>(
+        init: Init<Tui>,
+        width: u16,
+        height: u16,
+        func: impl (Fn() -> V) + 'static,
+    ) -> Self
+    where
+        V: View<Tui, Tml> + 'static,
+    {
+        let (context, config) = init.into_context();
+        set_viewport(Rect::new(0, 0, width, height));
+
+        let mut tui = Self {
+            app: TuiApp::new(Reactive(func), context, config),
+            terminal: Terminal::new(TestBackend::new(width, height))
+                .expect("the test backend does not fail"),
+            quit: false,
+        };
+        tui.dispatch();
+        tui
+    }
+
+    /// Handle a terminal event, then dispatch the signals it caused and render.
+    pub fn event(&mut self, event: Event) {
+        tui_wake::wake(tui_wake::Wake::Terminal(event));
+        self.dispatch();
+    }
+
+    /// Press and release a key.
+    pub fn press(&mut self, code: KeyCode) {
+        self.press_with(code, KeyModifiers::NONE);
+    }
+
+    /// Press and release a key while holding modifiers.
+    pub fn press_with(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        self.event(Event::Key(KeyEvent::new(code, modifiers)));
+        self.event(Event::Key(KeyEvent::new_with_kind(
+            code,
+            modifiers,
+            KeyEventKind::Release,
+        )));
+    }
+
+    /// Type text, one key press per character.
+    pub fn type_text(&mut self, text: &str) {
+        for char in text.chars() {
+            self.press(KeyCode::Char(char));
+        }
+    }
+
+    /// Send a mouse event at the given cell.
+    pub fn mouse(&mut self, kind: MouseEventKind, column: u16, row: u16) {
+        self.event(Event::Mouse(MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }));
+    }
+
+    /// Click the left mouse button at the given cell.
+    pub fn click(&mut self, column: u16, row: u16) {
+        self.mouse(MouseEventKind::Down(MouseButton::Left), column, row);
+        self.mouse(MouseEventKind::Up(MouseButton::Left), column, row);
+    }
+
+    /// Resize the buffer, as when the terminal is resized.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.terminal.backend_mut().resize(width, height);
+        self.event(Event::Resize(width, height));
+    }
+
+    /// Handle every pending wakeup: terminal events, signals and woken tasks,
+    /// rendering after each, until the app is idle.
+    pub fn dispatch(&mut self) {
+        loop {
+            if self
+                .app
+                .draw(&mut self.terminal)
+                .expect("the test backend does not fail")
+            {
+                continue;
+            }
+            let Some(wake) = tui_wake::try_wait() else {
+                return;
+            };
+            if self
+                .app
+                .wake(wake)
+                .expect("the test backend does not fail")
+                .is_break()
+            {
+                self.quit = true;
+            }
+        }
+    }
+
+    /// Whether the app was quit, e.g. by pressing one of [crate::TuiConfig::quit_keys].
+    pub fn has_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// The rendered buffer.
+    pub fn buffer(&self) -> &Buffer {
+        self.terminal.backend().buffer()
+    }
+
+    /// The rendered text, one line per row, without trailing spaces.
+    pub fn text(&self) -> String {
+        let buffer = self.buffer();
+        let mut text = String::new();
+        for y in 0..buffer.area.height {
+            let mut line = String::new();
+            let mut x = 0;
+            while x < buffer.area.width {
+                let symbol = buffer.get(x, y).symbol();
+                line.push_str(symbol);
+                // Wide characters cover the cells after them
+                x += Span::raw(symbol).width().max(1) as u16;
+            }
+            text.push_str(line.trim_end());
+            text.push('\n');
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use kano::prelude::app::*;
+
+    use super::*;
+    use crate::component::{Component, ComponentData, Layout, Style};
+
+    #[test]
+    fn renders_key_presses_and_resizes() {
+        let mut tui = HeadlessTui::new(12, 2, || {
+            let count = use_state(|| 0);
+            use_shortcut("x", move || count.update(|count| *count += 1)).unwrap();
+            let viewport = use_viewport();
+            format!("{} at {}x{}", count.get(), viewport.width, viewport.height)
+        });
+        assert_eq!("0 at 12x2\n\n", tui.text());
+
+        tui.type_text("xx");
+        assert_eq!("2 at 12x2\n\n", tui.text());
+
+        tui.resize(10, 1);
+        assert_eq!("2 at 10x1\n", tui.text());
+    }
+
+    #[test]
+    fn shortcut_handlers_see_the_latest_render() {
+        let mut tui = HeadlessTui::new(4, 1, || {
+            let count = use_state(|| 0);
+            let rendered = count.get();
+            use_shortcut("x", move || count.set(rendered + 1)).unwrap();
+            rendered.to_string()
+        });
+
+        tui.type_text("xxx");
+        assert_eq!("3\n", tui.text());
+    }
+
+    #[test]
+    fn key_presses_are_released() {
+        let mut tui = HeadlessTui::new(12, 6, || {
+            let keys = use_state(String::new);
+            Component {
+                data: Rc::new(ComponentData {
+                    layout: Layout::Paragraph,
+                    style: Style::default(),
+                }),
+                handlers: vec![
+                    on::keydown(move |_| keys.update(|keys| keys.push('v'))).into(),
+                    on::keyup(move |_| keys.update(|keys| keys.push('^'))).into(),
+                ],
+                bind: None,
+                focus: vec![],
+                children: (Reactive(move || keys.get()),),
+            }
+        });
+
+        tui.type_text("ab");
+        assert_eq!(Some("║ v^v^     ║"), tui.text().lines().nth(2));
+    }
+
+    #[test]
+    fn redraws_after_wakeups_from_other_threads() {
+        let mut tui = HeadlessTui::new(4, 1, || {
+            let count = use_state(|| 0);
+            use_shortcut("x", move || count.update(|count| *count += 1)).unwrap();
+            count.get().to_string()
+        });
+
+        // Like the terminal reader thread
+        let sender = tui_wake::sender();
+        std::thread::spawn(move || {
+            let key = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE);
+            sender
+                .send(tui_wake::Wake::Terminal(Event::Key(key)))
+                .unwrap();
+        })
+        .join()
+        .unwrap();
+        assert_eq!("0\n", tui.text());
+
+        tui.dispatch();
+        assert_eq!("1\n", tui.text());
+    }
+}
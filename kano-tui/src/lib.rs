@@ -1,10 +1,12 @@
 //! Kano is a work-in-progress GUI application framework written for and in Rust.
 use crossterm::{
-    event::{self, DisableMouseCapture, KeyCode, KeyEventKind},
+    event::{DisableMouseCapture, EnableMouseCapture},
     terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
 };
 use kano::{
+    attr::{Bind, Event},
+    event::{AnyEvent, InputEvent, Key, KeyEvent, SubmitEvent},
     markup::Markup,
     platform::{PlatformContext, PlatformInit},
     vdom::vnode::VNodeRef,
@@ -12,6 +14,7 @@ use kano::{
 use kano_svg::Svg1_1;
 use node_data::{NodeData, NodeKind};
 use ratatui::{
+    layout::Rect,
     prelude::{CrosstermBackend, Terminal},
     widgets::Paragraph,
     Frame,
@@ -22,16 +25,34 @@ use std::{
     panic,
     rc::Rc,
 };
+use tui_app::TuiApp;
 use tui_cursor::TuiCursor;
 use tui_state::TuiState;
+use tui_text_input::{Edit, TextCursor};
+use tui_wake::Wake;
 
 pub mod component;
+pub mod headless;
 pub mod node_data;
 
+pub use crossterm;
 pub use ratatui;
 
+pub use tui_config::TuiConfig;
+
+mod tui_app;
+mod tui_config;
 mod tui_cursor;
+mod tui_dispatch;
+mod tui_event;
+mod tui_executor;
+mod tui_focus;
+mod tui_mouse;
+mod tui_scroll;
 mod tui_state;
+mod tui_svg;
+mod tui_text_input;
+mod tui_wake;
 
 /// The TUI platform.
 pub struct Tui;
@@ -49,10 +70,11 @@ impl Markup<Tui> for Svg1_1 {
 
 impl kano::platform::Platform for Tui {
     type Markup = Tml;
+    type Config = TuiConfig;
 
     fn init(init: PlatformInit) -> PlatformContext {
         PlatformContext {
-            on_signal_tick: Rc::new(|| {}),
+            on_signal_tick: Rc::new(|| tui_wake::wake(Wake::Signal)),
             signal_dispatch: init.signal_dispatch,
             logger: Rc::new(|line| {
                 // FIXME: Don't reinvent logging?
@@ -73,8 +95,15 @@ impl kano::platform::Platform for Tui {
         }
     }
 
-    fn run(view: impl kano::View<Self, Tml>, context: PlatformContext) -> anyhow::Result<()> {
+    fn run(
+        view: impl kano::View<Self, Tml> + 'static,
+        context: PlatformContext,
+        config: TuiConfig,
+    ) -> anyhow::Result<()> {
         stdout().execute(EnterAlternateScreen)?;
+        if config.mouse {
+            stdout().execute(EnableMouseCapture)?;
+        }
         terminal::enable_raw_mode()?;
 
         let panic_hook = panic::take_hook();
@@ -86,71 +115,81 @@ impl kano::platform::Platform for Tui {
         let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
         terminal.clear()?;
 
-        let (mut cursor, root_node) = TuiCursor::new_root();
-        let state = view.init_const(&mut cursor);
-        std::mem::forget(state);
+        let terminal_events = tui_wake::read_terminal_events();
+        set_viewport(terminal.size()?);
 
-        let mut tui_state = TuiState {
-            currently_focused: 0,
-            focusable_counter: 0,
-            focused_event_handler: None,
-        };
+        let mut app = TuiApp::new(view, context, config);
 
+        // Every iteration follows a wakeup, so the loop sleeps until there is something new to draw
         loop {
-            tui_state.on_pre_frame();
-
-            terminal.draw(|frame| {
-                let area = frame.size();
-                let view = root_node.first_child().unwrap();
-                render_node(view, &mut tui_state, frame, area);
-            })?;
-
-            tui_state.on_post_frame();
-
-            if event::poll(std::time::Duration::from_millis(16))? {
-                if let event::Event::Key(key) = event::read()? {
-                    if key.kind == KeyEventKind::Press {
-                        match key.code {
-                            KeyCode::Char('q') => {
-                                break;
-                            }
-                            KeyCode::Up => {
-                                if tui_state.currently_focused > 0 {
-                                    tui_state.currently_focused -= 1;
-                                }
-                            }
-                            KeyCode::Down => {
-                                if tui_state.focusable_counter > 0
-                                    && tui_state.currently_focused < tui_state.focusable_counter - 1
-                                {
-                                    tui_state.currently_focused += 1;
-                                }
-                            }
-                            KeyCode::Char(' ') | KeyCode::Enter => {
-                                if let Some(handler) = tui_state.focused_event_handler.take() {
-                                    handler.invoke();
-                                    (context.signal_dispatch)();
-                                }
-                            }
-                            KeyCode::Backspace => {
-                                if kano::history::pop() {
-                                    (context.signal_dispatch)();
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
-                }
+            if app.draw(&mut terminal)? {
+                continue;
+            }
+            if app.wake(tui_wake::wait())?.is_break() {
+                break;
             }
         }
 
+        // Release the state of every view before leaving
+        drop(app);
+        drop(terminal_events);
+
         stdout().execute(LeaveAlternateScreen)?;
         reset_terminal()?;
         Ok(())
     }
 
-    fn spawn_task(_task: impl std::future::Future<Output = ()> + 'static) {
-        todo!();
+    fn spawn_task(task: impl std::future::Future<Output = ()> + 'static) {
+        tui_executor::spawn(task);
+    }
+}
+
+fn set_viewport(area: Rect) {
+    kano::viewport::set_viewport(kano::viewport::Viewport {
+        width: area.width as u32,
+        height: area.height as u32,
+    });
+}
+
+/// Let a key press edit the state of a focused two-way binding, and notify the focused node.
+///
+/// Returns `true` if the key was consumed.
+fn edit_bind(tui_state: &mut TuiState, bind: Bind, key_event: &KeyEvent) -> bool {
+    match bind {
+        Bind::Value(state) => {
+            let Some(node) = &tui_state.focused_node else {
+                return false;
+            };
+            let mut value = state.get();
+            let cursor = tui_state
+                .text_cursors
+                .entry(node.id())
+                .or_insert_with(|| TextCursor::at_end(&value));
+
+            match tui_text_input::edit(&mut value, cursor, key_event) {
+                Edit::Ignored => false,
+                Edit::Unchanged | Edit::Moved => true,
+                Edit::Changed => {
+                    state.set(value.clone());
+                    tui_state.emit(Event::Input, &AnyEvent::Input(InputEvent { value }));
+                    true
+                }
+                Edit::Submitted => {
+                    tui_state.emit(Event::Change, &AnyEvent::Input(InputEvent { value }));
+                    tui_state.emit(Event::Submit, &AnyEvent::Submit(SubmitEvent));
+                    true
+                }
+            }
+        }
+        Bind::Checked(state) => match key_event.key {
+            Key::Char(' ') | Key::Enter if !key_event.modifiers.is_command() => {
+                state.toggle();
+                let value = state.get().to_string();
+                tui_state.emit(Event::Change, &AnyEvent::Input(InputEvent { value }));
+                true
+            }
+            _ => false,
+        },
     }
 }
 
@@ -175,5 +214,9 @@ pub fn render_node(
         NodeKind::Component(data) => {
             data.render(node.clone(), tui_state, frame, area);
         }
+        NodeKind::Svg(_) => {
+            let lines = tui_svg::rasterize(&node, area.width, tui_state.svg_marker);
+            frame.render_widget(Paragraph::new(lines), area);
+        }
     }
 }
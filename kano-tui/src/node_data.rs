@@ -1,20 +1,30 @@
 use std::{fmt::Debug, rc::Rc};
 
-use kano::attr::{Click, On};
+use kano::{
+    attr::{Bind, Event, FocusAttr, On, Spent},
+    focus::FocusRef,
+};
 
-use crate::component::ComponentData;
+use crate::{component::ComponentData, tui_focus, tui_svg::SvgElement};
 
 #[derive(Clone, Debug)]
 pub struct NodeData {
     pub kind: NodeKind,
-    pub on_click: Option<On<Click>>,
+    pub handlers: Vec<On<Event>>,
+    /// Whether each of the `handlers` is spent, kept when they are replaced by a re-render.
+    pub spent: Vec<Spent>,
+    pub bind: Option<Bind>,
+    pub focus: Vec<FocusAttr>,
 }
 
 impl Default for NodeData {
     fn default() -> Self {
         Self {
             kind: NodeKind::Empty,
-            on_click: None,
+            handlers: vec![],
+            spent: vec![],
+            bind: None,
+            focus: vec![],
         }
     }
 }
@@ -23,14 +33,58 @@ impl From<NodeKind> for NodeData {
     fn from(value: NodeKind) -> Self {
         Self {
             kind: value,
-            on_click: None,
+            handlers: vec![],
+            spent: vec![],
+            bind: None,
+            focus: vec![],
         }
     }
 }
 
+impl NodeData {
+    /// Nodes with a binding, event handlers or focus properties can be focused.
+    ///
+    /// Capturing handlers only observe events targeting descendants,
+    /// so they don't make a node focusable on their own.
+    pub fn is_focusable(&self) -> bool {
+        self.bind.is_some()
+            || self.focus_ref().is_some()
+            || self
+                .focus
+                .iter()
+                .any(|focus| matches!(focus, FocusAttr::Autofocus(true) | FocusAttr::TabIndex(_)))
+            || self
+                .handlers
+                .iter()
+                .any(|handler| !handler.options().capture)
+    }
+
+    /// The [FocusRef] attached to this node.
+    pub fn focus_ref(&self) -> Option<FocusRef> {
+        tui_focus::focus_ref(&self.focus)
+    }
+
+    /// The position of this node in Tab order, see [FocusAttr::TabIndex].
+    pub fn tab_index(&self) -> i32 {
+        self.focus
+            .iter()
+            .find_map(|focus| match focus {
+                FocusAttr::TabIndex(index) => Some(*index),
+                _ => None,
+            })
+            .unwrap_or(0)
+    }
+
+    /// Whether focus is trapped within this node and its descendants.
+    pub fn is_focus_trap(&self) -> bool {
+        self.focus.contains(&FocusAttr::Trap(true))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum NodeKind {
     Empty,
     Text(String),
     Component(Rc<ComponentData>),
+    Svg(SvgElement),
 }
@@ -0,0 +1,228 @@
+//! A running app, driven by the terminal event loop or by the [crate::headless] test driver.
+
+use std::{io, ops::ControlFlow};
+
+use crossterm::event::{self, KeyEventKind};
+use kano::{
+    attr::Event,
+    event::{AnyEvent, ClickEvent, FocusEvent, Key},
+    keymap::Chord,
+    platform::PlatformContext,
+    root::Root,
+    vdom::vnode::VNodeRef,
+    View,
+};
+use ratatui::{backend::Backend, layout::Rect, Terminal};
+
+use crate::{
+    edit_bind, node_data::NodeData, render_node, set_viewport, tui_cursor::TuiCursor, tui_executor,
+    tui_mouse, tui_scroll, tui_state::TuiState, tui_wake::Wake, Tml, Tui, TuiConfig,
+};
+
+pub(crate) struct TuiApp {
+    root: Root<Tui>,
+    root_node: VNodeRef<NodeData>,
+    tui_state: TuiState,
+    context: PlatformContext,
+    config: TuiConfig,
+    focus_changed: bool,
+    quit_pending: bool,
+}
+
+impl TuiApp {
+    pub(crate) fn new(
+        view: impl View<Tui, Tml> + 'static,
+        context: PlatformContext,
+        config: TuiConfig,
+    ) -> Self {
+        let (cursor, root_node) = TuiCursor::new_root();
+        let root = Root::<Tui>::new(view, cursor);
+
+        let tui_state = TuiState {
+            currently_focused: Some(0),
+            svg_marker: config.svg_marker,
+            title: config.title(false),
+            ..Default::default()
+        };
+
+        Self {
+            root,
+            root_node,
+            tui_state,
+            context,
+            config,
+            focus_changed: false,
+            quit_pending: false,
+        }
+    }
+
+    /// Draw a frame.
+    ///
+    /// Returns `true` if a focus handler changed state while drawing, so that another frame is due.
+    pub(crate) fn draw<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<bool> {
+        self.root.apply_replacement();
+
+        let focus_update = self.tui_state.update_focus(&self.root_node);
+        if focus_update.moved {
+            self.tui_state
+                .emit(Event::Blur, &AnyEvent::Focus(FocusEvent));
+            self.focus_changed = true;
+        }
+        if focus_update.moved || focus_update.refs_changed {
+            self.dispatch_signals();
+        }
+
+        self.tui_state.on_pre_frame();
+        self.tui_state.title = self.config.title(self.quit_pending);
+
+        terminal.draw(|frame| {
+            let area = frame.size();
+            let view = self.root_node.first_child().unwrap();
+            render_node(view, &mut self.tui_state, frame, area);
+        })?;
+
+        self.tui_state.on_post_frame();
+
+        if std::mem::take(&mut self.focus_changed)
+            && self
+                .tui_state
+                .emit(Event::Focus, &AnyEvent::Focus(FocusEvent))
+                .handled
+        {
+            self.dispatch_signals();
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Handle a wakeup of the event loop.
+    pub(crate) fn wake(&mut self, wake: Wake) -> anyhow::Result<ControlFlow<()>> {
+        match wake {
+            Wake::Terminal(event) => return Ok(self.handle_event(event)),
+            Wake::Signal => self.dispatch_signals(),
+            Wake::Task(task_id) => {
+                tui_executor::poll(task_id);
+                self.dispatch_signals();
+            }
+            Wake::Error(error) => return Err(error.into()),
+        }
+        Ok(ControlFlow::Continue(()))
+    }
+
+    fn dispatch_signals(&self) {
+        (self.context.signal_dispatch)();
+    }
+
+    /// Handle a terminal event. Breaks when the user quits.
+    fn handle_event(&mut self, event: event::Event) -> ControlFlow<()> {
+        match event {
+            event::Event::Resize(width, height) => {
+                set_viewport(Rect::new(0, 0, width, height));
+                self.dispatch_signals();
+            }
+            event::Event::Mouse(mouse) => {
+                let handled = tui_mouse::handle_mouse(&mut self.tui_state, &mouse);
+                if handled {
+                    self.dispatch_signals();
+                }
+            }
+            event::Event::Key(key) => {
+                let key_event = crate::tui_event::key_event(&key);
+
+                if key.kind == KeyEventKind::Release {
+                    if self
+                        .tui_state
+                        .emit(Event::KeyUp, &AnyEvent::Key(key_event))
+                        .handled
+                    {
+                        self.dispatch_signals();
+                    }
+                } else if key.kind == KeyEventKind::Press {
+                    return self.handle_key_press(key_event);
+                }
+            }
+            _ => {}
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn handle_key_press(&mut self, key_event: kano::event::KeyEvent) -> ControlFlow<()> {
+        let Self {
+            tui_state,
+            context,
+            config,
+            focus_changed,
+            quit_pending,
+            ..
+        } = self;
+        let dispatch_signals = &context.signal_dispatch;
+
+        let dispatch = tui_state.emit(Event::KeyDown, &AnyEvent::Key(key_event));
+        if dispatch.handled {
+            dispatch_signals();
+        }
+        if dispatch.default_prevented {
+            return ControlFlow::Continue(());
+        }
+
+        // Text entry goes to the focused binding before shortcuts
+        if let Some(bind) = tui_state.focused_bind {
+            if edit_bind(tui_state, bind, &key_event) {
+                dispatch_signals();
+                return ControlFlow::Continue(());
+            }
+        }
+
+        if kano::keymap::dispatch(&key_event) {
+            dispatch_signals();
+            return ControlFlow::Continue(());
+        }
+
+        let chord = Chord::from_event(&key_event);
+        if config.quit_keys.contains(&chord) {
+            if !config.confirm_quit || *quit_pending {
+                return ControlFlow::Break(());
+            }
+            *quit_pending = true;
+            return ControlFlow::Continue(());
+        }
+        *quit_pending = false;
+
+        let previously_focused = tui_state.currently_focused;
+        let mut handled = false;
+
+        if let Some(direction) = config.spatial_direction(&chord) {
+            tui_state.focus_direction(direction);
+        } else if config.focus_previous_keys.contains(&chord) {
+            tui_state.focus_previous();
+        } else if config.focus_next_keys.contains(&chord) {
+            tui_state.focus_next();
+        } else if config.scroll_up_keys.contains(&chord) {
+            handled = tui_scroll::scroll_pages(tui_state, -1);
+        } else if config.scroll_down_keys.contains(&chord) {
+            handled = tui_scroll::scroll_pages(tui_state, 1);
+        } else if config.back_key == Some(chord) {
+            handled = kano::history::pop();
+        } else if let Key::Char(' ') | Key::Enter = key_event.key {
+            let click = ClickEvent {
+                modifiers: key_event.modifiers,
+                ..Default::default()
+            };
+            handled = tui_state
+                .emit(Event::Click, &AnyEvent::Click(click))
+                .handled;
+        }
+
+        if tui_state.currently_focused != previously_focused {
+            handled |= tui_state
+                .emit(Event::Blur, &AnyEvent::Focus(FocusEvent))
+                .handled;
+            *focus_changed = true;
+        }
+
+        if handled {
+            dispatch_signals();
+        }
+        ControlFlow::Continue(())
+    }
+}
@@ -0,0 +1,186 @@
+use kano::{
+    event::{Key, Modifiers},
+    keymap::Chord,
+};
+
+use ratatui::symbols::Marker;
+
+use crate::tui_focus::Direction;
+
+/// Configuration of the TUI event loop, set with [kano::Init::config].
+///
+/// ```ignore
+/// kano::init::<Tui>()
+///     .config(
+///         TuiConfig::default()
+///             .quit_keys([Chord::parse("Ctrl+Q").unwrap()])
+///             .confirm_quit(true),
+///     )
+///     .run_app(App)
+/// ```
+#[derive(Clone, Debug)]
+pub struct TuiConfig {
+    /// Keys that quit the app.
+    pub quit_keys: Vec<Chord>,
+    /// Whether a quit key has to be pressed twice in a row to quit.
+    pub confirm_quit: bool,
+    /// The key that navigates back in history, if any.
+    pub back_key: Option<Chord>,
+    /// Keys that move focus to the next focusable node.
+    pub focus_next_keys: Vec<Chord>,
+    /// Keys that move focus to the previous focusable node.
+    pub focus_previous_keys: Vec<Chord>,
+    /// Keys that scroll the scrolling box around the focused node up by a page.
+    pub scroll_up_keys: Vec<Chord>,
+    /// Keys that scroll the scrolling box around the focused node down by a page.
+    pub scroll_down_keys: Vec<Chord>,
+    /// Whether the arrow keys move focus to the closest focusable node on screen,
+    /// instead of through the focus keys above.
+    pub spatial_navigation: bool,
+    /// Whether to capture the mouse, for clicking, hovering and scrolling.
+    pub mouse: bool,
+    /// The characters SVG is drawn with: [Marker::Braille] for detail,
+    /// or [Marker::HalfBlock] for a color per half cell.
+    pub svg_marker: Marker,
+}
+
+impl Default for TuiConfig {
+    fn default() -> Self {
+        Self {
+            quit_keys: vec![
+                key(Key::Char('q')),
+                Chord {
+                    key: Key::Char('c'),
+                    modifiers: Modifiers {
+                        ctrl: true,
+                        ..Default::default()
+                    },
+                },
+            ],
+            confirm_quit: false,
+            back_key: Some(key(Key::Backspace)),
+            focus_next_keys: vec![key(Key::Down), key(Key::Tab)],
+            focus_previous_keys: vec![
+                key(Key::Up),
+                Chord {
+                    key: Key::Tab,
+                    modifiers: Modifiers {
+                        shift: true,
+                        ..Default::default()
+                    },
+                },
+            ],
+            scroll_up_keys: vec![key(Key::PageUp)],
+            scroll_down_keys: vec![key(Key::PageDown)],
+            spatial_navigation: false,
+            mouse: true,
+            svg_marker: Marker::Braille,
+        }
+    }
+}
+
+impl TuiConfig {
+    pub fn quit_keys(mut self, keys: impl IntoIterator<Item = Chord>) -> Self {
+        self.quit_keys = keys.into_iter().collect();
+        self
+    }
+
+    pub fn confirm_quit(mut self, confirm_quit: bool) -> Self {
+        self.confirm_quit = confirm_quit;
+        self
+    }
+
+    pub fn back_key(mut self, key: Option<Chord>) -> Self {
+        self.back_key = key;
+        self
+    }
+
+    pub fn focus_next_keys(mut self, keys: impl IntoIterator<Item = Chord>) -> Self {
+        self.focus_next_keys = keys.into_iter().collect();
+        self
+    }
+
+    pub fn focus_previous_keys(mut self, keys: impl IntoIterator<Item = Chord>) -> Self {
+        self.focus_previous_keys = keys.into_iter().collect();
+        self
+    }
+
+    pub fn scroll_up_keys(mut self, keys: impl IntoIterator<Item = Chord>) -> Self {
+        self.scroll_up_keys = keys.into_iter().collect();
+        self
+    }
+
+    pub fn scroll_down_keys(mut self, keys: impl IntoIterator<Item = Chord>) -> Self {
+        self.scroll_down_keys = keys.into_iter().collect();
+        self
+    }
+
+    pub fn spatial_navigation(mut self, spatial_navigation: bool) -> Self {
+        self.spatial_navigation = spatial_navigation;
+        self
+    }
+
+    pub fn mouse(mut self, mouse: bool) -> Self {
+        self.mouse = mouse;
+        self
+    }
+
+    pub fn svg_marker(mut self, marker: Marker) -> Self {
+        self.svg_marker = marker;
+        self
+    }
+
+    /// The direction of spatial navigation that a chord moves focus in, if enabled.
+    pub(crate) fn spatial_direction(&self, chord: &Chord) -> Option<Direction> {
+        if !self.spatial_navigation || chord.modifiers != Modifiers::default() {
+            return None;
+        }
+
+        match chord.key {
+            Key::Up => Some(Direction::Up),
+            Key::Down => Some(Direction::Down),
+            Key::Left => Some(Direction::Left),
+            Key::Right => Some(Direction::Right),
+            _ => None,
+        }
+    }
+
+    /// The frame title, telling how to quit.
+    pub(crate) fn title(&self, quit_pending: bool) -> String {
+        match (self.quit_keys.first(), quit_pending) {
+            (Some(chord), false) => format!("Kano TUI. Press {} to quit.", chord_name(chord)),
+            (Some(chord), true) => format!("Press {} again to quit.", chord_name(chord)),
+            (None, _) => "Kano TUI.".to_string(),
+        }
+    }
+}
+
+fn key(key: Key) -> Chord {
+    Chord {
+        key,
+        modifiers: Modifiers::default(),
+    }
+}
+
+fn chord_name(chord: &Chord) -> String {
+    let mut name = String::new();
+    for (held, modifier) in [
+        (chord.modifiers.ctrl, "Ctrl+"),
+        (chord.modifiers.alt, "Alt+"),
+        (chord.modifiers.shift, "Shift+"),
+        (chord.modifiers.meta, "Meta+"),
+    ] {
+        if held {
+            name.push_str(modifier);
+        }
+    }
+
+    match chord.key {
+        Key::Char(' ') => name.push_str("Space"),
+        Key::Char(char) => name.push(char),
+        Key::F(number) => name.push_str(&format!("F{number}")),
+        other => name.push_str(&format!("{other:?}")),
+    }
+
+    name
+}
@@ -1,7 +1,7 @@
 use std::rc::Rc;
 
 use kano::{
-    attr::{Click, On},
+    attr::{Bind, Event, FocusAttr, On, Spent},
     markup::NestMarkup,
     vdom::{
         vcursor::{Location, Mode, VCursor},
@@ -11,8 +11,9 @@ use kano::{
 use kano_svg::{Svg1_1, SvgMarkup};
 
 use crate::{
-    component::{ComponentData, Layout, Style},
+    component::ComponentData,
     node_data::{NodeData, NodeKind},
+    tui_svg::SvgElement,
     Tml, Tui,
 };
 
@@ -34,18 +35,75 @@ impl TuiCursor {
             .put_node(NodeData::from(NodeKind::Component(component)));
     }
 
-    fn current_node(&self) -> VNodeRef<NodeData> {
+    /// Replace the component data of the current node.
+    pub fn update_component(&mut self, component: Rc<ComponentData>) {
+        match &mut self.vcursor.location {
+            Location::Node(node) => {
+                node.0.borrow_mut().data.kind = NodeKind::Component(component);
+            }
+            other => panic!("{other:?}"),
+        }
+    }
+
+    fn update_svg(&mut self, func: impl FnOnce(&mut SvgElement)) {
+        match &mut self.vcursor.location {
+            Location::Node(node) => {
+                if let NodeKind::Svg(element) = &mut node.0.borrow_mut().data.kind {
+                    func(element);
+                }
+            }
+            other => panic!("{other:?}"),
+        }
+    }
+
+    pub(crate) fn current_node(&self) -> VNodeRef<NodeData> {
         match &self.vcursor.location {
             Location::Node(node) => node.clone(),
             _ => panic!(),
         }
     }
 
-    pub fn set_on_click(&mut self, on_click: Option<On<Click>>) {
+    pub fn set_handlers(&mut self, handlers: Vec<On<Event>>) {
+        match &mut self.vcursor.location {
+            Location::Node(node) => {
+                let data = &mut node.0.borrow_mut().data;
+
+                // A handler for the same event in the same position is the same handler re-rendered
+                data.spent = handlers
+                    .iter()
+                    .enumerate()
+                    .map(|(index, handler)| {
+                        match (data.handlers.get(index), data.spent.get(index)) {
+                            (Some(previous), Some(spent))
+                                if previous.event() == handler.event() =>
+                            {
+                                spent.clone()
+                            }
+                            _ => Spent::default(),
+                        }
+                    })
+                    .collect();
+                data.handlers = handlers;
+            }
+            other => panic!("{other:?}"),
+        }
+    }
+
+    pub fn set_bind(&mut self, bind: Option<Bind>) {
         match &mut self.vcursor.location {
             Location::Node(node) => {
                 let mut node_mut = node.0.borrow_mut();
-                node_mut.data.on_click = on_click;
+                node_mut.data.bind = bind;
+            }
+            other => panic!("{other:?}"),
+        }
+    }
+
+    pub fn set_focus(&mut self, focus: Vec<FocusAttr>) {
+        match &mut self.vcursor.location {
+            Location::Node(node) => {
+                let mut node_mut = node.0.borrow_mut();
+                node_mut.data.focus = focus;
             }
             other => panic!("{other:?}"),
         }
@@ -59,7 +117,8 @@ pub struct TuiEventHandle {
 impl Drop for TuiEventHandle {
     fn drop(&mut self) {
         let mut node_mut = self.node.0.borrow_mut();
-        node_mut.data.on_click = None;
+        node_mut.data.handlers.clear();
+        node_mut.data.spent.clear();
     }
 }
 
@@ -135,19 +194,25 @@ impl NestMarkup<Tui, Svg1_1> for Tml {
 }
 
 impl SvgMarkup<Tui> for Svg1_1 {
-    fn svg_element(_tag_name: &'static str, cursor: &mut Self::Cursor) {
-        // TODO: Not finished
-        cursor.set_component(Rc::new(ComponentData {
-            layout: Layout::Svg,
-            style: Style::default(),
-        }));
+    fn svg_element(tag_name: &'static str, cursor: &mut Self::Cursor) {
+        cursor
+            .vcursor
+            .put_node(NodeData::from(NodeKind::Svg(SvgElement::new(tag_name))));
     }
 
-    fn set_svg_attribute(_name: &str, _value: &str, _cursor: &mut Self::Cursor) {}
+    fn set_svg_attribute(name: &str, value: &str, cursor: &mut Self::Cursor) {
+        cursor.update_svg(|element| element.set_attribute(name, value));
+    }
 
-    fn remove_svg_attribute(_name: &str, _cursor: &mut Self::Cursor) {}
+    fn remove_svg_attribute(name: &str, cursor: &mut Self::Cursor) {
+        cursor.update_svg(|element| element.remove_attribute(name));
+    }
 
-    fn set_xml_attribute(_namespace: &str, _name: &str, _value: &str, _cursor: &mut Self::Cursor) {}
+    fn set_xml_attribute(_namespace: &str, name: &str, value: &str, cursor: &mut Self::Cursor) {
+        cursor.update_svg(|element| element.set_attribute(name, value));
+    }
 
-    fn remove_xml_attribute(_namespace: &str, _name: &str, _cursor: &mut Self::Cursor) {}
+    fn remove_xml_attribute(_namespace: &str, name: &str, cursor: &mut Self::Cursor) {
+        cursor.update_svg(|element| element.remove_attribute(name));
+    }
 }
@@ -0,0 +1,205 @@
+//! Dispatch of events through the node tree.
+//!
+//! An event targets one node. It first travels from the root down to the target's parent,
+//! invoking capturing handlers, then reaches the target, and then bubbles back up to the root,
+//! invoking the remaining handlers.
+
+use kano::{
+    attr::{Event, On, Spent},
+    event::AnyEvent,
+    vdom::vnode::VNodeRef,
+};
+
+use crate::node_data::NodeData;
+
+/// The outcome of dispatching an event to handlers.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Dispatch {
+    /// Some handler was invoked.
+    pub handled: bool,
+    /// Some handler asked for the built-in handling of the event to be skipped.
+    pub default_prevented: bool,
+    /// Some handler stopped the event from propagating further.
+    pub propagation_stopped: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Capture,
+    Target,
+    Bubble,
+}
+
+/// Dispatch `event` to `target` and its ancestors.
+pub fn dispatch(target: &VNodeRef<NodeData>, event: Event, payload: &AnyEvent) -> Dispatch {
+    let mut dispatch = Dispatch::default();
+
+    let mut ancestors = vec![];
+    let mut next_ancestor = target.parent();
+    while let Some(ancestor) = next_ancestor {
+        next_ancestor = ancestor.parent();
+        ancestors.push(ancestor);
+    }
+
+    for ancestor in ancestors.iter().rev() {
+        invoke_handlers(ancestor, event, payload, Phase::Capture, &mut dispatch);
+        if dispatch.propagation_stopped {
+            return dispatch;
+        }
+    }
+
+    invoke_handlers(target, event, payload, Phase::Target, &mut dispatch);
+
+    if event.bubbles() {
+        for ancestor in &ancestors {
+            if dispatch.propagation_stopped {
+                break;
+            }
+            invoke_handlers(ancestor, event, payload, Phase::Bubble, &mut dispatch);
+        }
+    }
+
+    dispatch
+}
+
+fn invoke_handlers(
+    node: &VNodeRef<NodeData>,
+    event: Event,
+    payload: &AnyEvent,
+    phase: Phase,
+    dispatch: &mut Dispatch,
+) {
+    // Clone the handlers, so that the node is not borrowed while they run
+    let handlers: Vec<(On<Event>, Spent)> = {
+        let data = &node.0.borrow().data;
+        data.handlers
+            .iter()
+            .zip(&data.spent)
+            .filter(|(handler, _)| *handler.event() == event)
+            .filter(|(handler, _)| match phase {
+                Phase::Capture => handler.options().capture,
+                Phase::Target => true,
+                Phase::Bubble => !handler.options().capture,
+            })
+            .map(|(handler, spent)| (handler.clone(), spent.clone()))
+            .collect()
+    };
+
+    for (handler, spent) in handlers {
+        if handler.invoke(payload, &spent) {
+            let options = handler.options();
+            dispatch.handled = true;
+            dispatch.default_prevented |= options.prevent_default;
+            dispatch.propagation_stopped |= options.stop_propagation;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use kano::{
+        event::{Key, KeyEvent, Modifiers},
+        markup::Cursor,
+        property::on,
+    };
+
+    use crate::{
+        component::{ComponentData, Layout, Style},
+        tui_cursor::TuiCursor,
+    };
+
+    use super::*;
+
+    fn logger(log: &Rc<RefCell<Vec<&'static str>>>, entry: &'static str) -> impl Fn(&KeyEvent) {
+        let log = log.clone();
+        move |_| log.borrow_mut().push(entry)
+    }
+
+    /// Build an outer node with a single inner child, returning the inner node
+    fn outer_and_inner(
+        outer_handlers: Vec<On<Event>>,
+        inner_handlers: Vec<On<Event>>,
+    ) -> (VNodeRef<NodeData>, VNodeRef<NodeData>) {
+        let data = Rc::new(ComponentData {
+            layout: Layout::Block,
+            style: Style::default(),
+        });
+        let (mut cursor, root) = TuiCursor::new_root();
+        cursor.set_component(data.clone());
+        cursor.set_handlers(outer_handlers);
+        cursor.enter_children();
+        cursor.set_component(data);
+        cursor.set_handlers(inner_handlers);
+
+        let inner = root.first_child().unwrap().first_child().unwrap();
+        (root, inner)
+    }
+
+    fn key_down() -> AnyEvent {
+        AnyEvent::Key(KeyEvent {
+            key: Key::Enter,
+            modifiers: Modifiers::default(),
+        })
+    }
+
+    #[test]
+    fn capture_then_target_then_bubble() {
+        let log = Rc::new(RefCell::new(vec![]));
+        let (_root, inner) = outer_and_inner(
+            vec![
+                on::keydown(logger(&log, "outer")).into(),
+                on::keydown(logger(&log, "outer capture")).capture().into(),
+            ],
+            vec![on::keydown(logger(&log, "inner")).into()],
+        );
+
+        let dispatch = dispatch(&inner, Event::KeyDown, &key_down());
+
+        assert!(dispatch.handled);
+        assert_eq!(vec!["outer capture", "inner", "outer"], *log.borrow());
+    }
+
+    #[test]
+    fn stop_propagation_skips_ancestors() {
+        let log = Rc::new(RefCell::new(vec![]));
+        let (_root, inner) = outer_and_inner(
+            vec![on::keydown(logger(&log, "outer")).into()],
+            vec![on::keydown(logger(&log, "inner"))
+                .stop_propagation()
+                .prevent_default()
+                .into()],
+        );
+
+        let dispatch = dispatch(&inner, Event::KeyDown, &key_down());
+
+        assert!(dispatch.propagation_stopped);
+        assert!(dispatch.default_prevented);
+        assert_eq!(vec!["inner"], *log.borrow());
+    }
+
+    #[test]
+    fn once_handler_stays_spent_when_re_rendered() {
+        let log = Rc::new(RefCell::new(vec![]));
+        let (_root, inner) = outer_and_inner(
+            vec![],
+            vec![on::keydown(logger(&log, "first render")).once().into()],
+        );
+
+        assert!(dispatch(&inner, Event::KeyDown, &key_down()).handled);
+        assert!(!dispatch(&inner, Event::KeyDown, &key_down()).handled);
+
+        TuiCursor::from_text_handle(&inner).set_handlers(vec![on::keydown(logger(
+            &log,
+            "re-render",
+        ))
+        .once()
+        .into()]);
+        assert!(!dispatch(&inner, Event::KeyDown, &key_down()).handled);
+
+        TuiCursor::from_text_handle(&inner).set_handlers(vec![on::click(|_| {}).once().into()]);
+        assert!(dispatch(&inner, Event::Click, &AnyEvent::Click(Default::default())).handled);
+        assert_eq!(vec!["first render"], *log.borrow());
+    }
+}
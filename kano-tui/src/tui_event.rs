@@ -0,0 +1,42 @@
+//! Translation of crossterm events into Kano's platform-agnostic event payloads.
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use kano::event::{Key, KeyEvent, Modifiers};
+
+pub(crate) fn key_event(event: &crossterm::event::KeyEvent) -> KeyEvent {
+    let key = match event.code {
+        KeyCode::Char(char) => Key::Char(char),
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Esc => Key::Escape,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Delete => Key::Delete,
+        KeyCode::Tab | KeyCode::BackTab => Key::Tab,
+        KeyCode::Left => Key::Left,
+        KeyCode::Right => Key::Right,
+        KeyCode::Up => Key::Up,
+        KeyCode::Down => Key::Down,
+        KeyCode::Home => Key::Home,
+        KeyCode::End => Key::End,
+        KeyCode::PageUp => Key::PageUp,
+        KeyCode::PageDown => Key::PageDown,
+        KeyCode::F(n) => Key::F(n),
+        _ => Key::Unidentified,
+    };
+
+    let mut modifiers = modifiers(event.modifiers);
+    // crossterm reports Shift-Tab as BackTab, not always with the shift modifier
+    if event.code == KeyCode::BackTab {
+        modifiers.shift = true;
+    }
+
+    KeyEvent { key, modifiers }
+}
+
+pub(crate) fn modifiers(modifiers: KeyModifiers) -> Modifiers {
+    Modifiers {
+        shift: modifiers.contains(KeyModifiers::SHIFT),
+        ctrl: modifiers.contains(KeyModifiers::CONTROL),
+        alt: modifiers.contains(KeyModifiers::ALT),
+        meta: modifiers.contains(KeyModifiers::SUPER) || modifiers.contains(KeyModifiers::META),
+    }
+}
@@ -0,0 +1,112 @@
+//! A single-threaded executor for tasks spawned on the TUI platform.
+//!
+//! Tasks are polled by the event loop. Waking a task wakes the loop, from any thread.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{mpsc::Sender, Arc},
+    task::{Context, Wake as TaskWake, Waker},
+};
+
+use crate::tui_wake::{self, Wake};
+
+type Task = Pin<Box<dyn Future<Output = ()>>>;
+
+thread_local! {
+    static TASKS: RefCell<HashMap<u64, Task>> = RefCell::new(HashMap::new());
+    static NEXT_TASK_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+struct TaskWaker {
+    task_id: u64,
+    sender: Sender<Wake>,
+}
+
+impl TaskWake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        // The loop may have quit already
+        let _ = self.sender.send(Wake::Task(self.task_id));
+    }
+}
+
+/// Spawn a task, to be polled first when the event loop wakes up.
+pub(crate) fn spawn(task: impl Future<Output = ()> + 'static) {
+    let task_id = NEXT_TASK_ID.replace(NEXT_TASK_ID.get() + 1);
+    TASKS.with_borrow_mut(|tasks| tasks.insert(task_id, Box::pin(task)));
+    tui_wake::wake(Wake::Task(task_id));
+}
+
+/// Poll a woken task, dropping it when it completes.
+pub(crate) fn poll(task_id: u64) {
+    // Take the task out while polling, so that it can spawn other tasks.
+    // Tasks that already completed may still be woken.
+    let Some(mut task) = TASKS.with_borrow_mut(|tasks| tasks.remove(&task_id)) else {
+        return;
+    };
+
+    let waker = Waker::from(Arc::new(TaskWaker {
+        task_id,
+        sender: tui_wake::sender(),
+    }));
+
+    if task
+        .as_mut()
+        .poll(&mut Context::from_waker(&waker))
+        .is_pending()
+    {
+        TASKS.with_borrow_mut(|tasks| tasks.insert(task_id, task));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{rc::Rc, task::Poll};
+
+    use super::*;
+
+    /// Yields once to the executor.
+    struct YieldNow(bool);
+
+    impl Future for YieldNow {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    #[test]
+    fn woken_tasks_run_to_completion() {
+        let steps = Rc::new(Cell::new(0));
+        spawn({
+            let steps = steps.clone();
+            async move {
+                steps.set(1);
+                YieldNow(false).await;
+                steps.set(2);
+            }
+        });
+        assert_eq!(0, steps.get());
+
+        for expected_steps in [1, 2] {
+            let Wake::Task(task_id) = tui_wake::wait() else {
+                panic!("expected a task wakeup");
+            };
+            poll(task_id);
+            assert_eq!(expected_steps, steps.get());
+        }
+        assert!(TASKS.with_borrow(|tasks| tasks.is_empty()));
+    }
+}
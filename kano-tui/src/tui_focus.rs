@@ -0,0 +1,315 @@
+//! Focus management of the node tree.
+//!
+//! Focusable nodes are ordered as they are rendered.
+//! Requests to move focus, from [kano::focus::FocusRef]s or `focus:autofocus`,
+//! are queued and resolved against that order before the next frame.
+
+use std::{cell::Cell, ops::Range, rc::Rc};
+
+use kano::{
+    attr::FocusAttr,
+    focus::{self, FocusRef, FocusTarget, Registration},
+    vdom::vnode::VNodeRef,
+};
+use ratatui::layout::Rect;
+
+use crate::node_data::{NodeData, NodeKind};
+
+thread_local! {
+    static FOCUS_REQUEST: Cell<Option<FocusRequest>> = const { Cell::new(None) };
+}
+
+/// A request to move focus, identifying nodes by id.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum FocusRequest {
+    Focus(u64),
+    Blur(u64),
+}
+
+/// Queue a focus request. Only the latest request is kept.
+pub(crate) fn request(request: FocusRequest) {
+    FOCUS_REQUEST.set(Some(request));
+}
+
+fn take_request() -> Option<FocusRequest> {
+    FOCUS_REQUEST.take()
+}
+
+struct NodeFocusTarget {
+    node_id: u64,
+}
+
+impl FocusTarget for NodeFocusTarget {
+    fn focus(&self) {
+        request(FocusRequest::Focus(self.node_id));
+    }
+
+    fn blur(&self) {
+        request(FocusRequest::Blur(self.node_id));
+    }
+}
+
+/// The [FocusRef] among the focus properties, if any.
+pub(crate) fn focus_ref(focus: &[FocusAttr]) -> Option<FocusRef> {
+    focus.iter().find_map(|focus| match focus {
+        FocusAttr::Ref(focus_ref) => Some(*focus_ref),
+        _ => None,
+    })
+}
+
+/// Attach the [FocusRef] among the focus properties, if any, to a node.
+pub(crate) fn register(node_id: u64, focus: &[FocusAttr]) -> Option<Registration> {
+    focus_ref(focus)
+        .map(|focus_ref| focus::register(focus_ref, Rc::new(NodeFocusTarget { node_id })))
+}
+
+/// A focusable node.
+pub(crate) struct Focusable {
+    pub node_id: u64,
+    pub focus_ref: Option<FocusRef>,
+    pub tab_index: i32,
+}
+
+/// The focusable nodes of a tree, in render order.
+#[derive(Default)]
+pub(crate) struct FocusOrder {
+    pub nodes: Vec<Focusable>,
+    /// The range of nodes within the active focus trap.
+    ///
+    /// When traps are nested or follow each other, the innermost and last one is active.
+    pub trap: Option<Range<usize>>,
+}
+
+impl FocusOrder {
+    pub fn of(root: &VNodeRef<NodeData>) -> Self {
+        let mut order = Self::default();
+        if let Some(view) = root.first_child() {
+            order.collect(&view);
+        }
+        order
+    }
+
+    pub fn position(&self, node_id: u64) -> Option<usize> {
+        self.nodes.iter().position(|node| node.node_id == node_id)
+    }
+
+    /// The nodes within `scope` that sequential navigation visits, in Tab order.
+    pub fn tab_order(&self, scope: Range<usize>) -> Vec<usize> {
+        let mut order: Vec<usize> = scope
+            .filter(|index| self.nodes[*index].tab_index >= 0)
+            .collect();
+
+        // The sort is stable, so nodes with equal tab index stay in render order
+        order.sort_by_key(|index| match self.nodes[*index].tab_index {
+            0 => i32::MAX,
+            tab_index => tab_index,
+        });
+        order
+    }
+
+    fn collect(&mut self, node: &VNodeRef<NodeData>) {
+        let node_borrow = node.0.borrow();
+        // Text and SVG are not focusable
+        let NodeKind::Component(_) = &node_borrow.data.kind else {
+            return;
+        };
+
+        let start = self.nodes.len();
+        if node_borrow.data.is_focusable() {
+            self.nodes.push(Focusable {
+                node_id: node.id(),
+                focus_ref: node_borrow.data.focus_ref(),
+                tab_index: node_borrow.data.tab_index(),
+            });
+        }
+
+        let mut next_child = node.first_child();
+        while let Some(child) = next_child {
+            self.collect(&child);
+            next_child = child.next_sibling();
+        }
+
+        let end = self.nodes.len();
+        if node_borrow.data.is_focus_trap()
+            && end > start
+            && self.trap.as_ref().is_none_or(|trap| start > trap.start)
+        {
+            self.trap = Some(start..end);
+        }
+    }
+}
+
+/// The outcome of [crate::tui_state::TuiState::update_focus].
+#[derive(Clone, Copy, Default, Debug)]
+pub struct FocusUpdate {
+    /// Focus moved to another node.
+    pub moved: bool,
+    /// The state of some [FocusRef] changed.
+    pub refs_changed: bool,
+}
+
+/// Resolve the pending focus request and the active trap.
+///
+/// Returns the newly focused index and the Tab order of the nodes that focus can move within.
+pub(crate) fn resolve(
+    order: &FocusOrder,
+    currently_focused: Option<usize>,
+) -> (Option<usize>, Vec<usize>) {
+    let mut focused = currently_focused;
+
+    match take_request() {
+        Some(FocusRequest::Focus(node_id)) => {
+            if let Some(position) = order.position(node_id) {
+                focused = Some(position);
+            }
+        }
+        Some(FocusRequest::Blur(node_id))
+            if focused.is_some() && focused == order.position(node_id) =>
+        {
+            focused = None;
+        }
+        _ => {}
+    }
+
+    let tab_order = match &order.trap {
+        Some(trap) => {
+            let tab_order = order.tab_order(trap.clone());
+            if !focused.is_some_and(|index| trap.contains(&index)) {
+                focused = Some(tab_order.first().copied().unwrap_or(trap.start));
+            }
+            tab_order
+        }
+        None => order.tab_order(0..order.nodes.len()),
+    };
+
+    (focused, tab_order)
+}
+
+/// A direction of spatial navigation.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// The candidate closest to `from` in `direction`, if any.
+///
+/// Distances across the direction count double, so that e.g. moving right prefers staying on the same line.
+pub(crate) fn nearest(
+    from: Rect,
+    candidates: impl Iterator<Item = (usize, Rect)>,
+    direction: Direction,
+) -> Option<usize> {
+    // Doubled centers, with rows doubled again since terminal cells are about twice as tall as wide
+    let center = |rect: Rect| {
+        (
+            rect.x as i32 * 2 + rect.width as i32,
+            (rect.y as i32 * 2 + rect.height as i32) * 2,
+        )
+    };
+    let (from_x, from_y) = center(from);
+
+    candidates
+        .filter_map(|(index, rect)| {
+            let (x, y) = center(rect);
+            let (dx, dy) = (x - from_x, y - from_y);
+            let (along, across) = match direction {
+                Direction::Up => (-dy, dx),
+                Direction::Down => (dy, dx),
+                Direction::Left => (-dx, dy),
+                Direction::Right => (dx, dy),
+            };
+
+            (along > 0).then_some((along + 2 * across.abs(), index))
+        })
+        .min()
+        .map(|(_, index)| index)
+}
+
+#[cfg(test)]
+mod tests {
+    use kano::{markup::Cursor, property::on};
+
+    use crate::{
+        component::{ComponentData, Layout, Style},
+        tui_cursor::TuiCursor,
+    };
+
+    use super::*;
+
+    #[test]
+    fn trap_confines_focus() {
+        let data = Rc::new(ComponentData {
+            layout: Layout::Block,
+            style: Style::default(),
+        });
+        let button = || vec![on::click(|_| {}).into()];
+
+        // outer { button, trap { button, button } }
+        let (mut cursor, root) = TuiCursor::new_root();
+        cursor.set_component(data.clone());
+        cursor.enter_children();
+        cursor.set_component(data.clone());
+        cursor.set_handlers(button());
+        cursor.set_component(data.clone());
+        cursor.set_focus(vec![FocusAttr::Trap(true)]);
+        cursor.enter_children();
+        cursor.set_component(data.clone());
+        cursor.set_handlers(button());
+        cursor.set_component(data);
+        cursor.set_handlers(button());
+
+        let order = FocusOrder::of(&root);
+        assert_eq!(3, order.nodes.len());
+        assert_eq!(Some(1..3), order.trap);
+
+        let (focused, scope) = resolve(&order, Some(0));
+        assert_eq!(Some(1), focused);
+        assert_eq!(vec![1, 2], scope);
+
+        let first_id = order.nodes[0].node_id;
+        request(FocusRequest::Focus(first_id));
+        let (focused, _) = resolve(&order, Some(2));
+        assert_eq!(Some(1), focused);
+    }
+
+    #[test]
+    fn tab_order_puts_positive_indices_first() {
+        let order = FocusOrder {
+            nodes: [0, 2, -1, 1, 0]
+                .into_iter()
+                .enumerate()
+                .map(|(node_id, tab_index)| Focusable {
+                    node_id: node_id as u64,
+                    focus_ref: None,
+                    tab_index,
+                })
+                .collect(),
+            trap: None,
+        };
+
+        assert_eq!(vec![3, 1, 0, 4], order.tab_order(0..5));
+    }
+
+    #[test]
+    fn nearest_prefers_the_same_row() {
+        let from = Rect::new(0, 0, 4, 1);
+        let candidates = [
+            (0, Rect::new(10, 0, 4, 1)),
+            (1, Rect::new(5, 2, 4, 1)),
+            (2, Rect::new(0, 2, 4, 1)),
+        ];
+
+        assert_eq!(
+            Some(0),
+            nearest(from, candidates.into_iter(), Direction::Right)
+        );
+        assert_eq!(
+            Some(2),
+            nearest(from, candidates.into_iter(), Direction::Down)
+        );
+        assert_eq!(None, nearest(from, candidates.into_iter(), Direction::Up));
+    }
+}
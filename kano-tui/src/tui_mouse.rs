@@ -0,0 +1,152 @@
+//! Mouse input, hit-tested against the rendered area of each node.
+
+use crossterm::event::{MouseButton, MouseEventKind};
+use kano::{
+    attr::Event,
+    event::{AnyEvent, ClickEvent, MouseEvent},
+    vdom::vnode::VNodeRef,
+};
+
+use crate::{
+    node_data::NodeData,
+    tui_dispatch::dispatch,
+    tui_event,
+    tui_focus::{self, FocusRequest},
+    tui_scroll::{self, ancestors},
+    tui_state::TuiState,
+};
+
+/// Dispatch a mouse event to the node under the pointer.
+///
+/// Returns whether some handler was invoked, or something scrolled.
+pub(crate) fn handle_mouse(tui_state: &mut TuiState, event: &crossterm::event::MouseEvent) -> bool {
+    let target = tui_state.hit_test(event.column, event.row);
+    let (x, y) = (event.column as i32, event.row as i32);
+    let modifiers = tui_event::modifiers(event.modifiers);
+
+    match event.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            let Some(target) = target else {
+                return false;
+            };
+            if let Some(focusable) =
+                ancestors(&target).find(|node| node.0.borrow().data.is_focusable())
+            {
+                tui_focus::request(FocusRequest::Focus(focusable.id()));
+            }
+
+            let click = ClickEvent { x, y, modifiers };
+            dispatch(&target, Event::Click, &AnyEvent::Click(click)).handled
+        }
+        MouseEventKind::Moved | MouseEventKind::Drag(_) => {
+            let mouse = AnyEvent::Mouse(MouseEvent { x, y, modifiers });
+            hover(tui_state, target, &mouse)
+        }
+        MouseEventKind::ScrollUp => scroll(tui_state, target, 0, -1),
+        MouseEventKind::ScrollDown => scroll(tui_state, target, 0, 1),
+        MouseEventKind::ScrollLeft => scroll(tui_state, target, -1, 0),
+        MouseEventKind::ScrollRight => scroll(tui_state, target, 1, 0),
+        _ => false,
+    }
+}
+
+/// Move the hover to `target`, emitting leave, enter and over events when it changes.
+fn hover(tui_state: &mut TuiState, target: Option<VNodeRef<NodeData>>, mouse: &AnyEvent) -> bool {
+    let target_id = target.as_ref().map(VNodeRef::id);
+    if tui_state.hovered_node.as_ref().map(VNodeRef::id) == target_id {
+        return false;
+    }
+
+    let mut handled = false;
+    if let Some(previous) = std::mem::replace(&mut tui_state.hovered_node, target.clone()) {
+        handled |= dispatch(&previous, Event::MouseLeave, mouse).handled;
+    }
+    if let Some(target) = &target {
+        handled |= dispatch(target, Event::MouseEnter, mouse).handled;
+        handled |= dispatch(target, Event::MouseOver, mouse).handled;
+    }
+    handled
+}
+
+fn scroll(
+    tui_state: &mut TuiState,
+    target: Option<VNodeRef<NodeData>>,
+    delta_left: i32,
+    delta_top: i32,
+) -> bool {
+    match target.and_then(|target| tui_scroll::scroller_of(tui_state, &target)) {
+        Some(scroller) => tui_scroll::scroll_by(tui_state, &scroller, delta_left, delta_top),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crossterm::event::KeyModifiers;
+    use kano::{markup::Cursor, property::on};
+    use ratatui::layout::Rect;
+
+    use crate::{
+        component::{ComponentData, Layout, Style},
+        tui_cursor::TuiCursor,
+    };
+
+    use super::*;
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> crossterm::event::MouseEvent {
+        crossterm::event::MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn events_target_the_innermost_node() {
+        let log = Rc::new(RefCell::new(vec![]));
+        let logger = |entry: &'static str| {
+            let log = log.clone();
+            move |_: &ClickEvent| log.borrow_mut().push(entry)
+        };
+        let data = Rc::new(ComponentData {
+            layout: Layout::Block,
+            style: Style::default(),
+        });
+
+        // outer { inner }
+        let (mut cursor, root) = TuiCursor::new_root();
+        cursor.set_component(data.clone());
+        cursor.set_handlers(vec![on::click(logger("outer")).into()]);
+        cursor.enter_children();
+        cursor.set_component(data);
+        cursor.set_handlers(vec![on::click(logger("inner")).into()]);
+
+        let outer = root.first_child().unwrap();
+        let inner = outer.first_child().unwrap();
+        let mut tui_state = TuiState {
+            node_rects: vec![
+                (outer.clone(), Rect::new(0, 0, 10, 4)),
+                (inner.clone(), Rect::new(0, 1, 5, 1)),
+            ],
+            ..Default::default()
+        };
+
+        assert!(handle_mouse(
+            &mut tui_state,
+            &mouse(MouseEventKind::Down(MouseButton::Left), 2, 1)
+        ));
+        assert!(handle_mouse(
+            &mut tui_state,
+            &mouse(MouseEventKind::Down(MouseButton::Left), 8, 1)
+        ));
+        assert_eq!(vec!["inner", "outer", "outer"], *log.borrow());
+
+        handle_mouse(&mut tui_state, &mouse(MouseEventKind::Moved, 2, 1));
+        assert!(tui_state.is_hovered(&inner));
+        handle_mouse(&mut tui_state, &mouse(MouseEventKind::Moved, 20, 20));
+        assert!(tui_state.hovered_node.is_none());
+    }
+}
@@ -0,0 +1,96 @@
+//! Scrolling of boxes, and of nodes that listen for scroll events.
+
+use kano::{
+    attr::Event,
+    event::{AnyEvent, ScrollEvent},
+    vdom::vnode::VNodeRef,
+};
+
+use crate::{node_data::NodeData, tui_dispatch::dispatch, tui_state::TuiState};
+
+/// The visible part of a scrolling box, as last rendered.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Viewport {
+    /// The number of visible rows.
+    pub height: u16,
+    /// The largest vertical offset, which shows the end of the content.
+    pub max_top: u16,
+}
+
+/// The closest node, starting from `node`, that scrolls:
+/// a scrolling box, or a node listening for scroll events.
+pub(crate) fn scroller_of(
+    tui_state: &TuiState,
+    node: &VNodeRef<NodeData>,
+) -> Option<VNodeRef<NodeData>> {
+    ancestors(node).find(|node| {
+        tui_state.viewports.contains_key(&node.id())
+            || node
+                .0
+                .borrow()
+                .data
+                .handlers
+                .iter()
+                .any(|handler| *handler.event() == Event::Scroll)
+    })
+}
+
+/// Scroll `scroller` by the given number of cells, and dispatch a scroll event if the offset changed.
+///
+/// Offsets never go below zero, and scrolling boxes don't scroll past the end of their content.
+/// Returns whether the offset changed.
+pub(crate) fn scroll_by(
+    tui_state: &mut TuiState,
+    scroller: &VNodeRef<NodeData>,
+    delta_left: i32,
+    delta_top: i32,
+) -> bool {
+    let viewport = tui_state.viewports.get(&scroller.id()).copied();
+    let offset = tui_state.scroll_offsets.entry(scroller.id()).or_default();
+
+    let mut scrolled = ScrollEvent {
+        left: (offset.left + delta_left).max(0),
+        top: (offset.top + delta_top).max(0),
+    };
+    if let Some(viewport) = viewport {
+        // Scrolling boxes wrap their content, so they only scroll vertically
+        scrolled.left = 0;
+        scrolled.top = scrolled.top.min(viewport.max_top as i32);
+    }
+    if scrolled == *offset {
+        return false;
+    }
+    *offset = scrolled;
+
+    dispatch(scroller, Event::Scroll, &AnyEvent::Scroll(scrolled));
+    true
+}
+
+/// Scroll by `pages` the scrolling box around the focused node, or else the first one on screen.
+pub(crate) fn scroll_pages(tui_state: &mut TuiState, pages: i32) -> bool {
+    let around_focus = tui_state.focused_node.as_ref().and_then(|focused| {
+        ancestors(focused).find(|node| tui_state.viewports.contains_key(&node.id()))
+    });
+    let Some(scroller) = around_focus.or_else(|| {
+        tui_state
+            .node_rects
+            .iter()
+            .map(|(node, _)| node)
+            .find(|node| tui_state.viewports.contains_key(&node.id()))
+            .cloned()
+    }) else {
+        return false;
+    };
+
+    // Keep a row of the previous page in view
+    let page = tui_state.viewports[&scroller.id()]
+        .height
+        .saturating_sub(1)
+        .max(1);
+    scroll_by(tui_state, &scroller, 0, pages * page as i32)
+}
+
+/// The node and its ancestors, innermost first.
+pub(crate) fn ancestors(node: &VNodeRef<NodeData>) -> impl Iterator<Item = VNodeRef<NodeData>> {
+    std::iter::successors(Some(node.clone()), VNodeRef::parent)
+}
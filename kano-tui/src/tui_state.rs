@@ -1,20 +1,170 @@
-use kano::attr::{Click, On};
+use std::collections::{HashMap, HashSet};
 
+use kano::{
+    attr::{Bind, Event},
+    event::{AnyEvent, ScrollEvent},
+    vdom::vnode::VNodeRef,
+};
+use ratatui::{layout::Rect, symbols::Marker};
+
+use crate::{
+    node_data::NodeData,
+    tui_dispatch::{dispatch, Dispatch},
+    tui_focus::{nearest, resolve, Direction, FocusOrder, FocusUpdate},
+    tui_scroll::Viewport,
+    tui_text_input::TextCursor,
+};
+
+#[derive(Default)]
 pub struct TuiState {
-    pub currently_focused: usize,
+    pub currently_focused: Option<usize>,
     pub focusable_counter: usize,
-    pub focused_event_handler: Option<On<Click>>,
+    /// The focusable indices that keyboard navigation moves between, in Tab order.
+    pub tab_order: Vec<usize>,
+    /// The rendered area of each focusable, by index.
+    pub focus_rects: Vec<Rect>,
+    pub focused_node: Option<VNodeRef<NodeData>>,
+    pub focused_bind: Option<Bind>,
+    /// The rendered area of each component node, in render order.
+    pub node_rects: Vec<(VNodeRef<NodeData>, Rect)>,
+    /// The node under the mouse pointer.
+    pub hovered_node: Option<VNodeRef<NodeData>>,
+    /// The scroll offset of each node that has been scrolled, by node id.
+    pub scroll_offsets: HashMap<u64, ScrollEvent>,
+    /// The cursor of each text input that has been focused, by node id.
+    pub text_cursors: HashMap<u64, TextCursor>,
+    /// The viewport of each scrolling box, by node id.
+    pub viewports: HashMap<u64, Viewport>,
+    /// The focused index when the last frame was rendered,
+    /// so that scrolling boxes only reveal the focused node when focus moves.
+    pub revealed_focus: Option<usize>,
+    /// How SVG is drawn.
+    pub svg_marker: Marker,
+    /// The title of the frame.
+    pub title: String,
 }
 
 impl TuiState {
     pub fn on_pre_frame(&mut self) {
         self.focusable_counter = 0;
-        self.focused_event_handler = None;
+        self.focus_rects.clear();
+        self.node_rects.clear();
+        self.viewports.clear();
+        self.focused_node = None;
+        self.focused_bind = None;
     }
 
     pub fn on_post_frame(&mut self) {
-        if self.currently_focused >= self.focusable_counter {
-            self.currently_focused = 0;
+        if self
+            .currently_focused
+            .is_some_and(|focused| focused >= self.focusable_counter)
+        {
+            self.currently_focused = Some(0);
+        }
+        self.revealed_focus = self.currently_focused;
+
+        // Forget the scroll offsets and text cursors of nodes that are no longer rendered
+        let rendered: HashSet<u64> = self.node_rects.iter().map(|(node, _)| node.id()).collect();
+        self.scroll_offsets.retain(|id, _| rendered.contains(id));
+        self.text_cursors.retain(|id, _| rendered.contains(id));
+    }
+
+    /// Apply pending focus requests and focus traps,
+    /// and update the state of every [kano::focus::FocusRef] in the tree.
+    ///
+    /// Must be called before [Self::on_pre_frame], so that [Self::emit] still targets the previously focused node.
+    pub fn update_focus(&mut self, root: &VNodeRef<NodeData>) -> FocusUpdate {
+        let order = FocusOrder::of(root);
+        let (focused, tab_order) = resolve(&order, self.currently_focused);
+
+        let mut update = FocusUpdate {
+            moved: focused != self.currently_focused,
+            refs_changed: false,
+        };
+        self.currently_focused = focused;
+        self.tab_order = tab_order;
+
+        for (index, node) in order.nodes.iter().enumerate() {
+            if let Some(focus_ref) = node.focus_ref {
+                update.refs_changed |= focus_ref.set_focused(Some(index) == focused);
+            }
+        }
+
+        update
+    }
+
+    /// Move focus to the previous focusable node in Tab order, wrapping around.
+    pub fn focus_previous(&mut self) {
+        self.step_focus(-1);
+    }
+
+    /// Move focus to the next focusable node in Tab order, wrapping around.
+    pub fn focus_next(&mut self) {
+        self.step_focus(1);
+    }
+
+    fn step_focus(&mut self, step: isize) {
+        let len = self.tab_order.len();
+        if len == 0 {
+            return;
+        }
+
+        let position = self
+            .currently_focused
+            .and_then(|focused| self.tab_order.iter().position(|index| *index == focused));
+        let next = match position {
+            Some(position) => (position as isize + step).rem_euclid(len as isize) as usize,
+            None if step > 0 => 0,
+            None => len - 1,
+        };
+
+        self.currently_focused = Some(self.tab_order[next]);
+    }
+
+    /// Move focus to the closest focusable node in `direction`, by rendered position.
+    pub fn focus_direction(&mut self, direction: Direction) {
+        let Some(from) = self
+            .currently_focused
+            .and_then(|focused| self.focus_rects.get(focused))
+        else {
+            return self.step_focus(1);
+        };
+
+        let candidates = self
+            .tab_order
+            .iter()
+            .filter(|index| Some(**index) != self.currently_focused)
+            .filter_map(|index| Some((*index, *self.focus_rects.get(*index)?)));
+
+        if let Some(index) = nearest(*from, candidates, direction) {
+            self.currently_focused = Some(index);
+        }
+    }
+
+    /// The innermost node rendered at a position, if any.
+    pub fn hit_test(&self, x: u16, y: u16) -> Option<VNodeRef<NodeData>> {
+        // Descendants are rendered after their ancestors
+        self.node_rects
+            .iter()
+            .rev()
+            .find(|(_, rect)| {
+                x >= rect.x && x < rect.x + rect.width && y >= rect.y && y < rect.y + rect.height
+            })
+            .map(|(node, _)| node.clone())
+    }
+
+    /// Whether the mouse pointer is over `node`.
+    pub fn is_hovered(&self, node: &VNodeRef<NodeData>) -> bool {
+        self.hovered_node
+            .as_ref()
+            .is_some_and(|hovered| hovered.id() == node.id())
+    }
+
+    /// Dispatch an event targeting the focused node.
+    pub fn emit(&self, event: Event, payload: &AnyEvent) -> Dispatch {
+        match &self.focused_node {
+            Some(node) => dispatch(node, event, payload),
+            None => Dispatch::default(),
         }
     }
 }
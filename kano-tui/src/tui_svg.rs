@@ -0,0 +1,908 @@
+//! Rasterization of SVG into terminal cells, with braille or half-block characters.
+//!
+//! Shapes are filled and stroked one dot at a time, in document order,
+//! each on its own canvas layer so that later shapes paint over earlier ones.
+//! Stroke widths, gradients, stylesheets and references with `<use>` are not supported.
+
+use std::str::FromStr;
+
+use kano::vdom::vnode::VNodeRef;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{
+        canvas::{Canvas, Painter, Shape},
+        Widget,
+    },
+};
+
+use crate::node_data::{NodeData, NodeKind};
+
+/// An SVG element, retained in the vdom with its attributes.
+#[derive(Clone, Debug)]
+pub struct SvgElement {
+    pub tag_name: &'static str,
+    pub attributes: Vec<(String, String)>,
+}
+
+impl SvgElement {
+    pub fn new(tag_name: &'static str) -> Self {
+        Self {
+            tag_name,
+            attributes: vec![],
+        }
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn set_attribute(&mut self, name: &str, value: &str) {
+        match self
+            .attributes
+            .iter_mut()
+            .find(|(attribute, _)| attribute == name)
+        {
+            Some((_, old_value)) => *old_value = value.to_string(),
+            None => self.attributes.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    pub fn remove_attribute(&mut self, name: &str) {
+        self.attributes.retain(|(attribute, _)| attribute != name);
+    }
+
+    /// A presentation attribute, which an inline `style` declaration overrides.
+    fn presentation(&self, name: &str) -> Option<&str> {
+        let from_style = self.attribute("style").and_then(|style| {
+            style.split(';').find_map(|declaration| {
+                let (property, value) = declaration.split_once(':')?;
+                (property.trim() == name).then_some(value.trim())
+            })
+        });
+        from_style.or_else(|| self.attribute(name))
+    }
+
+    fn number(&self, name: &str) -> f64 {
+        self.attribute(name).and_then(parse_length).unwrap_or(0.0)
+    }
+}
+
+/// Render an `<svg>` node as lines of text, at most `max_width` cells wide.
+///
+/// One SVG user unit maps to one dot, unless that makes the image wider than `max_width`.
+pub(crate) fn rasterize(
+    svg: &VNodeRef<NodeData>,
+    max_width: u16,
+    marker: Marker,
+) -> Vec<Line<'static>> {
+    let node_borrow = svg.0.borrow();
+    let NodeKind::Svg(element) = &node_borrow.data.kind else {
+        return vec![];
+    };
+
+    let width = element.attribute("width").and_then(parse_length);
+    let height = element.attribute("height").and_then(parse_length);
+    let view_box = element
+        .attribute("viewBox")
+        .map(parse_numbers)
+        .filter(|numbers| numbers.len() == 4 && numbers[2] > 0.0 && numbers[3] > 0.0)
+        .unwrap_or_else(|| vec![0.0, 0.0, width.unwrap_or(300.0), height.unwrap_or(150.0)]);
+    let (width, height) = match (width, height) {
+        (Some(width), Some(height)) => (width, height),
+        (Some(width), None) => (width, width * view_box[3] / view_box[2]),
+        (None, Some(height)) => (height * view_box[2] / view_box[3], height),
+        (None, None) => (view_box[2], view_box[3]),
+    };
+    if width <= 0.0 || height <= 0.0 || max_width == 0 {
+        return vec![];
+    }
+
+    let (dots_x, dots_y): (usize, usize) = match marker {
+        Marker::Braille => (2, 4),
+        Marker::HalfBlock => (1, 2),
+        _ => (1, 1),
+    };
+    let columns = ((width / dots_x as f64).ceil() as u16).clamp(1, max_width);
+    let dots_per_unit = (columns as usize * dots_x) as f64 / width;
+    // A buffer holds at most u16::MAX cells, so very tall images are cut off at the bottom
+    let rows =
+        ((height * dots_per_unit / dots_y as f64).ceil() as u16).clamp(1, u16::MAX / columns);
+
+    // Map user units to dots, through the view box
+    let scale_x = width / view_box[2] * dots_per_unit;
+    let scale_y = height / view_box[3] * dots_per_unit;
+    let to_dots = Transform([
+        scale_x,
+        0.0,
+        0.0,
+        scale_y,
+        -view_box[0] * scale_x,
+        -view_box[1] * scale_y,
+    ]);
+
+    let mut rasterizer = Rasterizer {
+        resolution: (columns as usize * dots_x, rows as usize * dots_y),
+        cell: (dots_x as f64, dots_y as f64),
+        layers: vec![],
+        labels: vec![],
+    };
+    rasterizer.children(svg, to_dots, Paint::default());
+
+    let area = Rect::new(0, 0, columns, rows);
+    let mut buffer = Buffer::empty(area);
+    Canvas::default()
+        .marker(marker)
+        .x_bounds([0.0, (columns - 1) as f64])
+        .y_bounds([0.0, (rows - 1) as f64])
+        .paint(|context| {
+            for layer in &rasterizer.layers {
+                context.draw(layer);
+                context.layer();
+            }
+            for (column, row, text, color) in &rasterizer.labels {
+                let span = Span::styled(text.clone(), Style::default().fg(*color));
+                context.print(*column, (rows - 1) as f64 - *row, span);
+            }
+        })
+        .render(area, &mut buffer);
+
+    lines(&buffer)
+}
+
+/// The cells of a buffer as lines of styled spans.
+///
+/// Blank cells are blank braille patterns rather than spaces, which wrapping text would trim.
+fn lines(buffer: &Buffer) -> Vec<Line<'static>> {
+    (0..buffer.area.height)
+        .map(|y| {
+            let mut spans: Vec<Span<'static>> = vec![];
+            for x in 0..buffer.area.width {
+                let cell = buffer.get(x, y);
+                let symbol = match cell.symbol() {
+                    " " => "\u{2800}",
+                    symbol => symbol,
+                };
+                match spans.last_mut() {
+                    Some(span) if span.style == cell.style() => {
+                        span.content.to_mut().push_str(symbol);
+                    }
+                    _ => spans.push(Span::styled(symbol.to_string(), cell.style())),
+                }
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// The fill and stroke inherited from ancestors.
+#[derive(Clone, Copy)]
+struct Paint {
+    fill: Option<Color>,
+    stroke: Option<Color>,
+}
+
+/// Unlike SVG, shapes are filled with the terminal's foreground color by default,
+/// so that icons show up on dark backgrounds.
+impl Default for Paint {
+    fn default() -> Self {
+        Self {
+            fill: Some(Color::Reset),
+            stroke: None,
+        }
+    }
+}
+
+impl Paint {
+    fn of(self, element: &SvgElement) -> Self {
+        Self {
+            fill: element.presentation("fill").map_or(self.fill, parse_color),
+            stroke: element
+                .presentation("stroke")
+                .map_or(self.stroke, parse_color),
+        }
+    }
+}
+
+/// Dots painted in one color, in grid coordinates.
+struct Dots {
+    dots: Vec<(usize, usize)>,
+    color: Color,
+}
+
+impl Shape for Dots {
+    fn draw(&self, painter: &mut Painter) {
+        for (x, y) in &self.dots {
+            painter.paint(*x, *y, self.color);
+        }
+    }
+}
+
+/// A 2D affine transformation `[a, b, c, d, e, f]`, as in SVG's `matrix()`.
+#[derive(Clone, Copy)]
+struct Transform([f64; 6]);
+
+impl Transform {
+    const IDENTITY: Self = Self([1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+
+    fn apply(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        let [a, b, c, d, e, f] = self.0;
+        (a * x + c * y + e, b * x + d * y + f)
+    }
+
+    /// This transformation after `inner`.
+    fn then(&self, inner: &Self) -> Self {
+        let [a, b, c, d, e, f] = self.0;
+        let [p, q, r, s, t, u] = inner.0;
+        Self([
+            a * p + c * q,
+            b * p + d * q,
+            a * r + c * s,
+            b * r + d * s,
+            a * t + c * u + e,
+            b * t + d * u + f,
+        ])
+    }
+
+    /// Parse a `transform` attribute. Unsupported functions are skipped.
+    fn parse(transform: &str) -> Self {
+        let mut result = Self::IDENTITY;
+        for function in transform.split_inclusive(')') {
+            let Some((name, arguments)) = function.split_once('(') else {
+                continue;
+            };
+            let arguments = parse_numbers(arguments.trim_end_matches(')'));
+            let next = match (name.trim().trim_start_matches(','), arguments.as_slice()) {
+                ("matrix", &[a, b, c, d, e, f]) => Self([a, b, c, d, e, f]),
+                ("translate", &[x]) => Self([1.0, 0.0, 0.0, 1.0, x, 0.0]),
+                ("translate", &[x, y]) => Self([1.0, 0.0, 0.0, 1.0, x, y]),
+                ("scale", &[s]) => Self([s, 0.0, 0.0, s, 0.0, 0.0]),
+                ("scale", &[x, y]) => Self([x, 0.0, 0.0, y, 0.0, 0.0]),
+                ("rotate", &[angle, ref center @ ..]) => {
+                    let (sin, cos) = angle.to_radians().sin_cos();
+                    let rotate = Self([cos, sin, -sin, cos, 0.0, 0.0]);
+                    match center {
+                        &[x, y] => Self([1.0, 0.0, 0.0, 1.0, x, y])
+                            .then(&rotate)
+                            .then(&Self([1.0, 0.0, 0.0, 1.0, -x, -y])),
+                        _ => rotate,
+                    }
+                }
+                _ => continue,
+            };
+            result = result.then(&next);
+        }
+        result
+    }
+}
+
+/// A shape as polylines in user units, each closed or open.
+type Outline = Vec<(Vec<(f64, f64)>, bool)>;
+
+struct Rasterizer {
+    /// The size of the dot grid.
+    resolution: (usize, usize),
+    /// The number of dots in a cell, horizontally and vertically.
+    cell: (f64, f64),
+    layers: Vec<Dots>,
+    /// Text as (column, row, text, color).
+    labels: Vec<(f64, f64, String, Color)>,
+}
+
+impl Rasterizer {
+    fn children(&mut self, node: &VNodeRef<NodeData>, transform: Transform, paint: Paint) {
+        let mut next_child = node.first_child();
+        while let Some(child) = next_child {
+            self.element(&child, transform, paint);
+            next_child = child.next_sibling();
+        }
+    }
+
+    fn element(&mut self, node: &VNodeRef<NodeData>, transform: Transform, paint: Paint) {
+        let node_borrow = node.0.borrow();
+        let NodeKind::Svg(element) = &node_borrow.data.kind else {
+            return;
+        };
+        if element.presentation("display") == Some("none") {
+            return;
+        }
+
+        let transform = match element.attribute("transform") {
+            Some(attribute) => transform.then(&Transform::parse(attribute)),
+            None => transform,
+        };
+        let paint = paint.of(element);
+
+        let outline = match element.tag_name {
+            "g" | "a" | "svg" | "switch" => {
+                self.children(node, transform, paint);
+                return;
+            }
+            "text" => {
+                self.text(node, element, transform, paint);
+                return;
+            }
+            "rect" => rect(element),
+            "circle" => {
+                let r = element.number("r");
+                ellipse(element, r, r)
+            }
+            "ellipse" => ellipse(element, element.number("rx"), element.number("ry")),
+            "line" => vec![(
+                vec![
+                    (element.number("x1"), element.number("y1")),
+                    (element.number("x2"), element.number("y2")),
+                ],
+                false,
+            )],
+            "polyline" | "polygon" => {
+                let points = parse_numbers(element.attribute("points").unwrap_or_default())
+                    .chunks_exact(2)
+                    .map(|point| (point[0], point[1]))
+                    .collect();
+                vec![(points, element.tag_name == "polygon")]
+            }
+            "path" => parse_path(element.attribute("d").unwrap_or_default()),
+            // Definitions, styles, metadata and unsupported elements are not shown
+            _ => return,
+        };
+
+        let outline: Outline = outline
+            .into_iter()
+            .map(|(points, closed)| {
+                let points = points.into_iter().map(|point| transform.apply(point));
+                (points.collect(), closed)
+            })
+            .collect();
+
+        if let Some(color) = paint.fill.filter(|_| element.tag_name != "line") {
+            let dots = self.fill(&outline);
+            self.layers.push(Dots { dots, color });
+        }
+        if let Some(color) = paint.stroke {
+            let dots = self.stroke(&outline);
+            self.layers.push(Dots { dots, color });
+        }
+    }
+
+    fn text(
+        &mut self,
+        node: &VNodeRef<NodeData>,
+        element: &SvgElement,
+        transform: Transform,
+        paint: Paint,
+    ) {
+        let mut text = String::new();
+        collect_text(node, &mut text);
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let Some(color) = paint.fill.filter(|_| !text.is_empty()) else {
+            return;
+        };
+
+        // The baseline is at the bottom of the text, so the text is shown in the row above it
+        let (x, y) = transform.apply((element.number("x"), element.number("y")));
+        let column = (x / self.cell.0).floor();
+        let row = ((y - 1.0) / self.cell.1).floor();
+        let rows = (self.resolution.1 as f64 / self.cell.1).floor();
+        if column >= 0.0 && row >= 0.0 && row < rows {
+            self.labels.push((column, row, text, color));
+        }
+    }
+
+    /// The dots whose centers are inside the outline, by the even-odd rule.
+    fn fill(&self, outline: &Outline) -> Vec<(usize, usize)> {
+        let mut dots = vec![];
+        for row in 0..self.resolution.1 {
+            let y = row as f64 + 0.5;
+            let mut crossings: Vec<f64> = outline
+                .iter()
+                .flat_map(|(points, _)| edges(points, true))
+                .filter(|(from, to)| (from.1 <= y) != (to.1 <= y))
+                .map(|(from, to)| from.0 + (y - from.1) * (to.0 - from.0) / (to.1 - from.1))
+                .collect();
+            crossings.sort_by(f64::total_cmp);
+
+            for span in crossings.chunks_exact(2) {
+                let first = (span[0] - 0.5).ceil().max(0.0) as usize;
+                let last = (span[1] - 0.5).floor();
+                if last < 0.0 {
+                    continue;
+                }
+                let last = (last as usize).min(self.resolution.0.saturating_sub(1));
+                dots.extend((first..=last).map(|column| (column, row)));
+            }
+        }
+        dots
+    }
+
+    /// The dots along the edges of the outline, one dot wide.
+    fn stroke(&self, outline: &Outline) -> Vec<(usize, usize)> {
+        let mut dots = vec![];
+        let bounds = (self.resolution.0 as f64, self.resolution.1 as f64);
+        for (points, closed) in outline {
+            for (from, to) in edges(points, *closed) {
+                // Only step along the part of the edge that is in the image
+                let Some((from, to)) = clip(from, to, bounds) else {
+                    continue;
+                };
+                let steps = (to.0 - from.0).abs().max((to.1 - from.1).abs()).ceil() as usize;
+                for step in 0..=steps {
+                    let t = if steps == 0 {
+                        0.0
+                    } else {
+                        step as f64 / steps as f64
+                    };
+                    let x = (from.0 + (to.0 - from.0) * t).floor();
+                    let y = (from.1 + (to.1 - from.1) * t).floor();
+                    if x >= 0.0
+                        && y >= 0.0
+                        && (x as usize) < self.resolution.0
+                        && (y as usize) < self.resolution.1
+                    {
+                        dots.push((x as usize, y as usize));
+                    }
+                }
+            }
+        }
+        dots
+    }
+}
+
+/// The part of the line segment from `from` to `to` that is inside `(0, 0)..=bounds`, if any.
+fn clip(from: (f64, f64), to: (f64, f64), bounds: (f64, f64)) -> Option<((f64, f64), (f64, f64))> {
+    if ![from.0, from.1, to.0, to.1]
+        .iter()
+        .all(|number| number.is_finite())
+    {
+        return None;
+    }
+
+    // Liang-Barsky: narrow the range of the segment parameter by each edge of the bounds
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let (mut enter, mut leave) = (0.0_f64, 1.0_f64);
+    for (p, q) in [
+        (-dx, from.0),
+        (dx, bounds.0 - from.0),
+        (-dy, from.1),
+        (dy, bounds.1 - from.1),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else if p < 0.0 {
+            enter = enter.max(q / p);
+        } else {
+            leave = leave.min(q / p);
+        }
+    }
+    if enter > leave {
+        return None;
+    }
+
+    let at = |t: f64| (from.0 + dx * t, from.1 + dy * t);
+    Some((at(enter), at(leave)))
+}
+
+/// The consecutive pairs of points, and the closing edge back to the first point if `closed`.
+fn edges(
+    points: &[(f64, f64)],
+    closed: bool,
+) -> impl Iterator<Item = ((f64, f64), (f64, f64))> + '_ {
+    let closing = match (points.first(), points.last()) {
+        (Some(first), Some(last)) if closed && points.len() > 2 => Some((*last, *first)),
+        _ => None,
+    };
+    points
+        .windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .chain(closing)
+}
+
+fn collect_text(node: &VNodeRef<NodeData>, text: &mut String) {
+    let mut next_child = node.first_child();
+    while let Some(child) = next_child {
+        match &child.0.borrow().data.kind {
+            NodeKind::Text(child_text) => text.push_str(child_text),
+            NodeKind::Svg(_) => collect_text(&child, text),
+            _ => {}
+        }
+        next_child = child.next_sibling();
+    }
+}
+
+fn rect(element: &SvgElement) -> Outline {
+    let (x, y) = (element.number("x"), element.number("y"));
+    let (width, height) = (element.number("width"), element.number("height"));
+    let points = vec![
+        (x, y),
+        (x + width, y),
+        (x + width, y + height),
+        (x, y + height),
+    ];
+    vec![(points, true)]
+}
+
+fn ellipse(element: &SvgElement, rx: f64, ry: f64) -> Outline {
+    const SEGMENTS: usize = 48;
+    let (cx, cy) = (element.number("cx"), element.number("cy"));
+    let points = (0..SEGMENTS)
+        .map(|segment| {
+            let (sin, cos) = (segment as f64 / SEGMENTS as f64 * std::f64::consts::TAU).sin_cos();
+            (cx + rx * cos, cy + ry * sin)
+        })
+        .collect();
+    vec![(points, true)]
+}
+
+/// Parse path data into polylines, flattening curves.
+///
+/// Elliptical arcs are drawn as straight lines to their end points.
+fn parse_path(data: &str) -> Outline {
+    const CURVE_SEGMENTS: usize = 16;
+
+    let mut outline: Outline = vec![];
+    let mut points: Vec<(f64, f64)> = vec![];
+    let mut current = (0.0, 0.0);
+    let mut start = (0.0, 0.0);
+    // The last control point of a curve, for the smooth curve commands
+    let mut last_control: Option<(char, (f64, f64))> = None;
+
+    let mut tokens = PathTokens { rest: data };
+    let mut command = None;
+    while !tokens.is_empty() {
+        // Numbers without a command repeat the previous command
+        let Some(command_char) = tokens.command().or(command) else {
+            break;
+        };
+        command = Some(command_char);
+        if command_char.eq_ignore_ascii_case(&'z') {
+            if !points.is_empty() {
+                outline.push((std::mem::take(&mut points), true));
+            }
+            current = start;
+            command = None;
+            last_control = None;
+            continue;
+        }
+
+        let relative = command_char.is_ascii_lowercase();
+        let origin = if relative { current } else { (0.0, 0.0) };
+        let point = |tokens: &mut PathTokens| -> Option<(f64, f64)> {
+            Some((tokens.number()? + origin.0, tokens.number()? + origin.1))
+        };
+
+        let control = match command_char.to_ascii_uppercase() {
+            'M' => {
+                let Some(to) = point(&mut tokens) else { break };
+                if points.len() > 1 {
+                    outline.push((std::mem::take(&mut points), false));
+                }
+                points = vec![to];
+                (current, start) = (to, to);
+                // Further coordinate pairs are lines
+                command = Some(if relative { 'l' } else { 'L' });
+                None
+            }
+            'L' => {
+                let Some(to) = point(&mut tokens) else { break };
+                points.push(to);
+                current = to;
+                None
+            }
+            'H' => {
+                let Some(x) = tokens.number() else { break };
+                current = (x + origin.0, current.1);
+                points.push(current);
+                None
+            }
+            'V' => {
+                let Some(y) = tokens.number() else { break };
+                current = (current.0, y + origin.1);
+                points.push(current);
+                None
+            }
+            'C' | 'S' => {
+                let first = if command_char.eq_ignore_ascii_case(&'c') {
+                    let Some(first) = point(&mut tokens) else {
+                        break;
+                    };
+                    first
+                } else {
+                    reflect(last_control, 'C', current)
+                };
+                let (Some(second), Some(to)) = (point(&mut tokens), point(&mut tokens)) else {
+                    break;
+                };
+                let from = current;
+                points.extend((1..=CURVE_SEGMENTS).map(|segment| {
+                    let t = segment as f64 / CURVE_SEGMENTS as f64;
+                    let u = 1.0 - t;
+                    let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+                    (
+                        a * from.0 + b * first.0 + c * second.0 + d * to.0,
+                        a * from.1 + b * first.1 + c * second.1 + d * to.1,
+                    )
+                }));
+                current = to;
+                Some(('C', second))
+            }
+            'Q' | 'T' => {
+                let control = if command_char.eq_ignore_ascii_case(&'q') {
+                    let Some(control) = point(&mut tokens) else {
+                        break;
+                    };
+                    control
+                } else {
+                    reflect(last_control, 'Q', current)
+                };
+                let Some(to) = point(&mut tokens) else { break };
+                let from = current;
+                points.extend((1..=CURVE_SEGMENTS).map(|segment| {
+                    let t = segment as f64 / CURVE_SEGMENTS as f64;
+                    let u = 1.0 - t;
+                    let (a, b, c) = (u * u, 2.0 * u * t, t * t);
+                    (
+                        a * from.0 + b * control.0 + c * to.0,
+                        a * from.1 + b * control.1 + c * to.1,
+                    )
+                }));
+                current = to;
+                Some(('Q', control))
+            }
+            'A' => {
+                // Radii, rotation and flags
+                for _ in 0..5 {
+                    if tokens.number().is_none() {
+                        break;
+                    }
+                }
+                let Some(to) = point(&mut tokens) else { break };
+                points.push(to);
+                current = to;
+                None
+            }
+            _ => break,
+        };
+        last_control = control;
+    }
+
+    if points.len() > 1 {
+        outline.push((points, false));
+    }
+    outline
+}
+
+/// The first control point of a smooth curve: the last control point of a curve of `kind`,
+/// reflected about the current point, or else the current point itself.
+fn reflect(
+    last_control: Option<(char, (f64, f64))>,
+    kind: char,
+    current: (f64, f64),
+) -> (f64, f64) {
+    match last_control {
+        Some((last_kind, control)) if last_kind == kind => {
+            (2.0 * current.0 - control.0, 2.0 * current.1 - control.1)
+        }
+        _ => current,
+    }
+}
+
+enum PathToken {
+    Command(char),
+    Number(f64),
+}
+
+struct PathTokens<'d> {
+    rest: &'d str,
+}
+
+impl<'d> PathTokens<'d> {
+    fn next_token(&mut self) -> Option<PathToken> {
+        self.rest = self
+            .rest
+            .trim_start_matches(|char: char| char.is_whitespace() || char == ',');
+        let char = self.rest.chars().next()?;
+        if char.is_ascii_alphabetic() && !matches!(char, 'e' | 'E') {
+            self.rest = &self.rest[1..];
+            return Some(PathToken::Command(char));
+        }
+
+        // A number ends where another one starts, e.g. in "1-2" or "0.5.5"
+        let mut end = 0;
+        let mut seen_dot = false;
+        let mut seen_exponent = false;
+        for (index, char) in self.rest.char_indices() {
+            let previous = self.rest[..index].chars().last();
+            let continues = match char {
+                '0'..='9' => true,
+                '+' | '-' => index == 0 || matches!(previous, Some('e' | 'E')),
+                '.' if !seen_dot && !seen_exponent => {
+                    seen_dot = true;
+                    true
+                }
+                'e' | 'E' if !seen_exponent && index > 0 => {
+                    seen_exponent = true;
+                    true
+                }
+                _ => false,
+            };
+            if !continues {
+                break;
+            }
+            end = index + char.len_utf8();
+        }
+
+        let (number, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        match number.parse() {
+            Ok(number) => Some(PathToken::Number(number)),
+            // Skip what can't be parsed
+            Err(_) if end == 0 => {
+                self.rest = &self.rest[char.len_utf8()..];
+                self.next_token()
+            }
+            Err(_) => None,
+        }
+    }
+
+    /// Take the next token if it's a number.
+    fn number(&mut self) -> Option<f64> {
+        let rest = self.rest;
+        match self.next_token() {
+            Some(PathToken::Number(number)) => Some(number),
+            _ => {
+                self.rest = rest;
+                None
+            }
+        }
+    }
+
+    /// Take the next token if it's a command.
+    fn command(&mut self) -> Option<char> {
+        let rest = self.rest;
+        match self.next_token() {
+            Some(PathToken::Command(command)) => Some(command),
+            _ => {
+                self.rest = rest;
+                None
+            }
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.rest
+            .trim_start_matches(|char: char| char.is_whitespace() || char == ',')
+            .is_empty()
+    }
+}
+
+/// Parse a list of numbers separated by whitespace and/or commas.
+fn parse_numbers(list: &str) -> Vec<f64> {
+    let mut tokens = PathTokens { rest: list };
+    let mut numbers = vec![];
+    while let Some(number) = tokens.number() {
+        numbers.push(number);
+    }
+    numbers
+}
+
+/// Parse a length in user units, or in pixels. Relative lengths are not supported.
+fn parse_length(length: &str) -> Option<f64> {
+    length.trim().trim_end_matches("px").parse().ok()
+}
+
+/// Parse a paint, where `None` means no paint.
+fn parse_color(paint: &str) -> Option<Color> {
+    let paint = paint.trim();
+    match paint {
+        "none" | "transparent" => None,
+        "currentColor" => Some(Color::Reset),
+        _ if paint.len() == 4 && paint.is_ascii() && paint.starts_with('#') => {
+            let digit = |index: usize| u8::from_str_radix(&paint[index..index + 1], 16).ok();
+            match (digit(1), digit(2), digit(3)) {
+                (Some(r), Some(g), Some(b)) => Some(Color::Rgb(r * 17, g * 17, b * 17)),
+                _ => Some(Color::Reset),
+            }
+        }
+        // Gradients and patterns are shown in the foreground color
+        _ => Some(Color::from_str(paint).unwrap_or(Color::Reset)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kano::markup::Cursor;
+    use kano_svg::{Svg1_1, SvgMarkup};
+
+    use crate::tui_cursor::TuiCursor;
+
+    use super::*;
+
+    fn element(cursor: &mut TuiCursor, tag_name: &'static str, attributes: &[(&str, &str)]) {
+        Svg1_1::svg_element(tag_name, cursor);
+        for (name, value) in attributes {
+            Svg1_1::set_svg_attribute(name, value, cursor);
+        }
+    }
+
+    fn text_of(line: &Line) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn shapes_are_rasterized_in_document_order() {
+        let (mut cursor, root) = TuiCursor::new_root();
+        element(&mut cursor, "svg", &[("width", "8"), ("height", "8")]);
+        cursor.enter_children();
+        element(
+            &mut cursor,
+            "rect",
+            &[("width", "8"), ("height", "8"), ("fill", "#f00")],
+        );
+        element(
+            &mut cursor,
+            "path",
+            &[("d", "M0 0h4v4h-4z"), ("fill", "blue")],
+        );
+        cursor.exit_children();
+
+        let lines = rasterize(&root.first_child().unwrap(), 10, Marker::HalfBlock);
+        let text: Vec<String> = lines.iter().map(text_of).collect();
+        assert_eq!(vec!["████████"; 4], text);
+
+        let color_at = |line: &Line, column: usize| {
+            let mut spans = line
+                .spans
+                .iter()
+                .flat_map(|span| span.content.chars().map(move |_| span.style.fg));
+            spans.nth(column).flatten()
+        };
+        assert_eq!(Some(Color::Blue), color_at(&lines[0], 3));
+        assert_eq!(Some(Color::Rgb(255, 0, 0)), color_at(&lines[0], 4));
+        assert_eq!(Some(Color::Rgb(255, 0, 0)), color_at(&lines[2], 0));
+
+        // Braille packs the same image into fewer cells
+        let lines = rasterize(&root.first_child().unwrap(), 10, Marker::Braille);
+        let text: Vec<String> = lines.iter().map(text_of).collect();
+        assert_eq!(vec!["⣿⣿⣿⣿"; 2], text);
+    }
+
+    #[test]
+    fn non_ascii_hex_colors_are_not_parsed() {
+        assert_eq!(Some(Color::Rgb(255, 0, 170)), parse_color("#f0a"));
+        assert_eq!(Some(Color::Reset), parse_color("#éa"));
+    }
+
+    #[test]
+    fn huge_images_and_strokes_are_clipped() {
+        let (mut cursor, root) = TuiCursor::new_root();
+        element(&mut cursor, "svg", &[("width", "20"), ("height", "100000")]);
+        cursor.enter_children();
+        element(
+            &mut cursor,
+            "line",
+            &[
+                ("x1", "-1e15"),
+                ("y1", "0"),
+                ("x2", "1e15"),
+                ("y2", "0.5"),
+                ("stroke", "red"),
+            ],
+        );
+        cursor.exit_children();
+
+        let lines = rasterize(&root.first_child().unwrap(), 10, Marker::Braille);
+        assert_eq!((u16::MAX / 10) as usize, lines.len());
+        assert_eq!("⠉".repeat(10), text_of(&lines[0]));
+    }
+}
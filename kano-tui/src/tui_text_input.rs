@@ -0,0 +1,271 @@
+//! Text inputs: editing their value with the cursor and selection, and rendering them.
+
+use std::ops::Range;
+
+use kano::event::{Key, KeyEvent};
+use ratatui::{
+    style::{Modifier, Style},
+    text::Span,
+};
+
+use crate::component::TextInputLayout;
+
+/// The cursor and selection of a text input, in characters.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct TextCursor {
+    /// Where the cursor is.
+    pub position: usize,
+    /// The other end of the selection, which is empty when the anchor is at the cursor.
+    pub anchor: usize,
+}
+
+impl TextCursor {
+    /// A cursor at the end of `value`, where typing continues.
+    pub fn at_end(value: &str) -> Self {
+        let end = value.chars().count();
+        Self {
+            position: end,
+            anchor: end,
+        }
+    }
+
+    /// The selected characters.
+    pub fn selection(&self) -> Range<usize> {
+        self.position.min(self.anchor)..self.position.max(self.anchor)
+    }
+
+    /// Keep the cursor within a value of `len` characters, which may have changed since the cursor moved.
+    fn clamp(&mut self, len: usize) {
+        self.position = self.position.min(len);
+        self.anchor = self.anchor.min(len);
+    }
+}
+
+/// What a key press did to a text input.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Edit {
+    /// The key press is not for the text input.
+    Ignored,
+    /// The key press is for the text input, but did nothing, e.g. Backspace at the start.
+    Unchanged,
+    /// The cursor or the selection moved.
+    Moved,
+    /// The value changed.
+    Changed,
+    /// The value was submitted with Enter.
+    Submitted,
+}
+
+/// Let a key press edit `value` at `cursor`.
+///
+/// Typing replaces the selection, and Shift extends the selection while moving the cursor.
+/// Editing keys are for the text input even when they change nothing, so that e.g. Backspace
+/// doesn't navigate back. Only moving past either end with the arrows is ignored,
+/// so that the key press can move focus instead.
+pub(crate) fn edit(value: &mut String, cursor: &mut TextCursor, key_event: &KeyEvent) -> Edit {
+    let len = value.chars().count();
+    cursor.clamp(len);
+
+    let modifiers = key_event.modifiers;
+    if modifiers.is_command() {
+        return match key_event.key {
+            Key::Char('a') if modifiers.ctrl => consume(move_to(
+                cursor,
+                TextCursor {
+                    position: len,
+                    anchor: 0,
+                },
+            )),
+            Key::Backspace | Key::Delete => Edit::Unchanged,
+            _ => Edit::Ignored,
+        };
+    }
+
+    let selection = cursor.selection();
+    let moved = |position: usize| TextCursor {
+        position,
+        anchor: if modifiers.shift {
+            cursor.anchor
+        } else {
+            position
+        },
+    };
+
+    match key_event.key {
+        Key::Left if !selection.is_empty() && !modifiers.shift => {
+            move_to(cursor, moved(selection.start))
+        }
+        Key::Right if !selection.is_empty() && !modifiers.shift => {
+            move_to(cursor, moved(selection.end))
+        }
+        Key::Left => move_to(cursor, moved(cursor.position.saturating_sub(1))),
+        Key::Right => move_to(cursor, moved((cursor.position + 1).min(len))),
+        Key::Home => consume(move_to(cursor, moved(0))),
+        Key::End => consume(move_to(cursor, moved(len))),
+        Key::Char(char) => replace(value, cursor, selection, &char.to_string()),
+        Key::Backspace if selection.is_empty() => match cursor.position {
+            0 => Edit::Unchanged,
+            position => replace(value, cursor, position - 1..position, ""),
+        },
+        Key::Delete if selection.is_empty() => match cursor.position {
+            position if position == len => Edit::Unchanged,
+            position => replace(value, cursor, position..position + 1, ""),
+        },
+        Key::Backspace | Key::Delete => replace(value, cursor, selection, ""),
+        Key::Enter => Edit::Submitted,
+        _ => Edit::Ignored,
+    }
+}
+
+/// The edit, where ignoring the key press is doing nothing with it instead.
+fn consume(edit: Edit) -> Edit {
+    match edit {
+        Edit::Ignored => Edit::Unchanged,
+        edit => edit,
+    }
+}
+
+fn move_to(cursor: &mut TextCursor, moved: TextCursor) -> Edit {
+    if *cursor == moved {
+        return Edit::Ignored;
+    }
+    *cursor = moved;
+    Edit::Moved
+}
+
+/// Replace the characters in `range` with `text`, and put the cursor after it.
+fn replace(value: &mut String, cursor: &mut TextCursor, range: Range<usize>, text: &str) -> Edit {
+    let byte_index = |chars: usize| {
+        value
+            .char_indices()
+            .nth(chars)
+            .map_or(value.len(), |(index, _)| index)
+    };
+    let bytes = byte_index(range.start)..byte_index(range.end);
+    value.replace_range(bytes, text);
+
+    let position = range.start + text.chars().count();
+    *cursor = TextCursor {
+        position,
+        anchor: position,
+    };
+    Edit::Changed
+}
+
+/// The spans showing a text input with `value`, and its cursor when it's focused.
+///
+/// The input ends with a blank cell, where the cursor is when it's at the end.
+pub(crate) fn spans(
+    value: &str,
+    layout: &TextInputLayout,
+    cursor: Option<TextCursor>,
+    style: Style,
+) -> Vec<Span<'static>> {
+    let (shown, style): (Vec<char>, Style) = match &layout.placeholder {
+        Some(placeholder) if value.is_empty() => (
+            placeholder.chars().collect(),
+            style.add_modifier(Modifier::DIM),
+        ),
+        _ if layout.password => (value.chars().map(|_| '•').collect(), style),
+        _ => (value.chars().collect(), style),
+    };
+
+    // The cursor only covers a cell when nothing is selected
+    let highlighted = cursor.map(|cursor| match cursor.selection() {
+        selection if selection.is_empty() => cursor.position..cursor.position + 1,
+        selection => selection,
+    });
+
+    let mut spans: Vec<Span<'static>> = vec![];
+    let mut run = String::new();
+    let mut run_highlighted = false;
+    for (index, char) in shown.into_iter().chain([' ']).enumerate() {
+        let is_highlighted = highlighted
+            .as_ref()
+            .is_some_and(|highlighted| highlighted.contains(&index));
+        if is_highlighted != run_highlighted && !run.is_empty() {
+            spans.push(run_span(std::mem::take(&mut run), style, run_highlighted));
+        }
+        run_highlighted = is_highlighted;
+        run.push(char);
+    }
+    spans.push(run_span(run, style, run_highlighted));
+    spans
+}
+
+fn run_span(text: String, style: Style, highlighted: bool) -> Span<'static> {
+    if highlighted {
+        Span::styled(text, style.add_modifier(Modifier::REVERSED))
+    } else {
+        Span::styled(text, style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kano::event::Modifiers;
+
+    use super::*;
+
+    fn press(value: &mut String, cursor: &mut TextCursor, key: Key, shift: bool) -> Edit {
+        let modifiers = Modifiers {
+            shift,
+            ..Default::default()
+        };
+        edit(value, cursor, &KeyEvent { key, modifiers })
+    }
+
+    #[test]
+    fn typing_replaces_the_selection() {
+        let mut value = "héllo".to_string();
+        let mut cursor = TextCursor::at_end(&value);
+
+        assert_eq!(
+            Edit::Ignored,
+            press(&mut value, &mut cursor, Key::Right, false)
+        );
+        assert_eq!(
+            Edit::Moved,
+            press(&mut value, &mut cursor, Key::Left, false)
+        );
+        assert_eq!(Edit::Moved, press(&mut value, &mut cursor, Key::Left, true));
+        assert_eq!(Edit::Moved, press(&mut value, &mut cursor, Key::Left, true));
+        assert_eq!(2..4, cursor.selection());
+
+        assert_eq!(
+            Edit::Changed,
+            press(&mut value, &mut cursor, Key::Char('a'), false)
+        );
+        assert_eq!("héao", value);
+        assert_eq!(
+            TextCursor {
+                position: 3,
+                anchor: 3
+            },
+            cursor
+        );
+
+        press(&mut value, &mut cursor, Key::Home, false);
+        assert_eq!(
+            Edit::Unchanged,
+            press(&mut value, &mut cursor, Key::Backspace, false)
+        );
+        assert_eq!(
+            Edit::Unchanged,
+            press(&mut value, &mut cursor, Key::Home, false)
+        );
+        assert_eq!(
+            Edit::Ignored,
+            press(&mut value, &mut cursor, Key::Left, false)
+        );
+        assert_eq!(
+            Edit::Changed,
+            press(&mut value, &mut cursor, Key::Delete, false)
+        );
+        assert_eq!("éao", value);
+        assert_eq!(
+            Edit::Submitted,
+            press(&mut value, &mut cursor, Key::Enter, false)
+        );
+    }
+}
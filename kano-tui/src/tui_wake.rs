@@ -0,0 +1,144 @@
+//! Wakeups of the event loop, which sleeps until there is something to do.
+
+use std::{
+    io,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use crossterm::event;
+
+/// A reason for the event loop to wake up.
+pub(crate) enum Wake {
+    /// The terminal sent an event.
+    Terminal(event::Event),
+    /// Signals are pending dispatch.
+    Signal,
+    /// A spawned task was woken, and should be polled.
+    Task(u64),
+    /// Reading terminal events failed.
+    Error(io::Error),
+}
+
+thread_local! {
+    static CHANNEL: (Sender<Wake>, Receiver<Wake>) = mpsc::channel();
+}
+
+/// Wake up the event loop.
+pub(crate) fn wake(wake: Wake) {
+    CHANNEL.with(|(sender, _)| {
+        // The receiver lives as long as the sender
+        let _ = sender.send(wake);
+    });
+}
+
+/// A sender that can wake up the event loop from other threads.
+pub(crate) fn sender() -> Sender<Wake> {
+    CHANNEL.with(|(sender, _)| sender.clone())
+}
+
+/// Sleep until the next wakeup.
+pub(crate) fn wait() -> Wake {
+    CHANNEL.with(|(_, receiver)| {
+        receiver
+            .recv()
+            .expect("the sender lives as long as the receiver")
+    })
+}
+
+/// Take the next wakeup, if there is one, without sleeping.
+pub(crate) fn try_wait() -> Option<Wake> {
+    CHANNEL.with(|(_, receiver)| receiver.try_recv().ok())
+}
+
+/// How long the terminal reader waits for an event before checking whether it should stop.
+///
+/// A blocking [event::read] can't be interrupted, so a reader blocked on it would outlive the app
+/// and swallow input meant for whatever reads the terminal next.
+/// Polling is a deliberate trade-off: the reader thread wakes up this often even when nothing happens,
+/// so the process is never fully idle, but it only wakes the event loop when there is an event.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Forwards terminal events to the event loop from a background thread, until dropped.
+pub(crate) struct TerminalEvents {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for TerminalEvents {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            // The thread stops within a poll interval, see [POLL_INTERVAL]
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Forward terminal events to the event loop, from a background thread.
+pub(crate) fn read_terminal_events() -> TerminalEvents {
+    let sender = sender();
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread = std::thread::spawn({
+        let stop = stop.clone();
+        move || {
+            while !stop.load(Ordering::Relaxed) {
+                let wake = match event::poll(POLL_INTERVAL) {
+                    Ok(false) => continue,
+                    Ok(true) => event::read(),
+                    Err(error) => Err(error),
+                };
+                let (wake, failed) = match wake {
+                    Ok(event) => (Wake::Terminal(event), false),
+                    Err(error) => (Wake::Error(error), true),
+                };
+                if sender.send(wake).is_err() || failed {
+                    break;
+                }
+            }
+        }
+    });
+
+    TerminalEvents {
+        stop,
+        thread: Some(thread),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wakes_from_other_threads_arrive_in_order() {
+        let sender = sender();
+        std::thread::spawn(move || {
+            sender.send(Wake::Task(1)).unwrap();
+            sender.send(Wake::Signal).unwrap();
+        })
+        .join()
+        .unwrap();
+        wake(Wake::Task(2));
+
+        assert!(matches!(try_wait(), Some(Wake::Task(1))));
+        assert!(matches!(try_wait(), Some(Wake::Signal)));
+        assert!(matches!(wait(), Wake::Task(2)));
+        assert!(try_wait().is_none());
+    }
+
+    #[test]
+    fn dropping_stops_the_terminal_reader() {
+        // Dropping joins the reader thread, so this returns once it has stopped:
+        // Without a terminal the reader fails, and otherwise it notices the stop flag
+        drop(read_terminal_events());
+
+        while let Some(wake) = try_wait() {
+            assert!(matches!(wake, Wake::Error(_)));
+        }
+    }
+}
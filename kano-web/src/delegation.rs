@@ -0,0 +1,242 @@
+//! Event delegation, for handlers with `on:click|delegate`, or every handler with the `event-delegation` feature.
+//!
+//! Instead of one DOM listener per handler, the document gets one listener per event type.
+//! Elements with handlers are tagged with an id, and the document listener
+//! walks the event path from the target outwards, invoking the handlers registered for each id.
+//!
+//! Only bubbling, non-capturing handlers are delegated.
+
+use std::cell::RefCell;
+
+use fnv::FnvHashMap;
+use gloo::events::{EventListener, EventListenerOptions};
+use kano::attr::{Event, On};
+use wasm_bindgen::JsValue;
+
+use crate::{
+    document,
+    event::any_event,
+    web_cursor::{get_property, SharedHandler},
+};
+
+/// The JS property of an element holding its delegation id.
+const ID_PROPERTY: &str = "__kanoDelegationId";
+
+thread_local! {
+    static DELEGATION: RefCell<Delegation> = RefCell::new(Delegation::default());
+}
+
+#[derive(Default)]
+struct Delegation {
+    next_id: u32,
+    handlers: FnvHashMap<u32, Vec<(u32, SharedHandler)>>,
+    listeners: FnvHashMap<Event, EventListener>,
+}
+
+/// Whether a handler should be delegated instead of getting its own listener.
+pub(crate) fn should_delegate(on_event: &On<Event>) -> bool {
+    let options = on_event.options();
+    (options.delegate || cfg!(feature = "event-delegation"))
+        && on_event.event().bubbles()
+        && !options.capture
+}
+
+/// A handler registered in the delegation table.
+/// It is unregistered when dropped.
+pub struct Delegated {
+    element_id: u32,
+    handler_id: u32,
+}
+
+impl Drop for Delegated {
+    fn drop(&mut self) {
+        DELEGATION.with_borrow_mut(|delegation| {
+            if let Some(handlers) = delegation.handlers.get_mut(&self.element_id) {
+                handlers.retain(|(handler_id, _)| *handler_id != self.handler_id);
+                if handlers.is_empty() {
+                    delegation.handlers.remove(&self.element_id);
+                }
+            }
+        });
+    }
+}
+
+/// Register a handler for events targeting `element` or its descendants.
+pub(crate) fn delegate(element: &web_sys::Element, handler: SharedHandler) -> Delegated {
+    let event = *handler.on_event.borrow().event();
+
+    DELEGATION.with_borrow_mut(|delegation| {
+        let element_id = match get_property(element, ID_PROPERTY).as_f64() {
+            Some(id) => id as u32,
+            None => {
+                let id = delegation.next_id();
+                js_sys::Reflect::set(
+                    element,
+                    &JsValue::from_str(ID_PROPERTY),
+                    &JsValue::from_f64(id as f64),
+                )
+                .unwrap();
+                id
+            }
+        };
+        let handler_id = delegation.next_id();
+
+        delegation
+            .handlers
+            .entry(element_id)
+            .or_default()
+            .push((handler_id, handler));
+
+        delegation
+            .listeners
+            .entry(event)
+            .or_insert_with(|| document_listener(event));
+
+        Delegated {
+            element_id,
+            handler_id,
+        }
+    })
+}
+
+impl Delegation {
+    fn next_id(&mut self) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+}
+
+fn document_listener(kind: Event) -> EventListener {
+    EventListener::new_with_options(
+        &document(),
+        kind.name(),
+        EventListenerOptions::enable_prevent_default(),
+        move |event| {
+            for target in event.composed_path().iter() {
+                let Some(element_id) = get_property(&target, ID_PROPERTY).as_f64() else {
+                    continue;
+                };
+
+                // Don't hold the borrow while the handlers run
+                let handlers: Vec<(On<Event>, SharedHandler)> =
+                    DELEGATION.with_borrow(|delegation| {
+                        delegation
+                            .handlers
+                            .get(&(element_id as u32))
+                            .into_iter()
+                            .flatten()
+                            .map(|(_, handler)| {
+                                (handler.on_event.borrow().clone(), handler.clone())
+                            })
+                            .filter(|(on_event, _)| *on_event.event() == kind)
+                            .collect()
+                    });
+
+                let mut stopped = false;
+                for (on_event, handler) in handlers {
+                    let options = on_event.options();
+                    if on_event.invoke(&any_event(kind, event), &handler.spent) {
+                        if options.prevent_default {
+                            event.prevent_default();
+                        }
+                        stopped |= options.stop_propagation;
+                    }
+                }
+
+                if stopped {
+                    event.stop_propagation();
+                    break;
+                }
+            }
+        },
+    )
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use std::rc::Rc;
+
+    use kano::{attr::Spent, property::on};
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_test::*;
+
+    use super::*;
+    use crate::web_cursor::SlotHandler;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    type Log = Rc<RefCell<Vec<&'static str>>>;
+
+    fn logger(log: &Log, entry: &'static str) -> On<Event> {
+        let log = log.clone();
+        on::click(move |_| log.borrow_mut().push(entry))
+            .delegate()
+            .into()
+    }
+
+    fn handler(on_event: On<Event>) -> SharedHandler {
+        Rc::new(SlotHandler {
+            on_event: RefCell::new(on_event),
+            spent: Spent::default(),
+        })
+    }
+
+    /// A button inside a div, in the document
+    fn outer_and_inner() -> (web_sys::Element, web_sys::HtmlElement) {
+        let outer = document().create_element("div").unwrap();
+        let inner = document().create_element("button").unwrap();
+        outer.append_child(&inner).unwrap();
+        document().body().unwrap().append_child(&outer).unwrap();
+        (outer, inner.unchecked_into())
+    }
+
+    #[wasm_bindgen_test]
+    fn dispatches_from_the_target_outwards() {
+        let log = Log::default();
+        let (outer, inner) = outer_and_inner();
+
+        let _outer = delegate(&outer, handler(logger(&log, "outer")));
+        let _inner = delegate(&inner, handler(logger(&log, "inner")));
+        let listeners = DELEGATION.with_borrow(|delegation| delegation.listeners.len());
+        let _inner_again = delegate(&inner, handler(logger(&log, "inner again")));
+
+        inner.click();
+        assert_eq!(vec!["inner", "inner again", "outer"], *log.borrow());
+
+        // The document listener is shared by all handlers of the event type
+        assert_eq!(
+            listeners,
+            DELEGATION.with_borrow(|delegation| delegation.listeners.len())
+        );
+        outer.remove();
+    }
+
+    #[wasm_bindgen_test]
+    fn stop_propagation_skips_outer_handlers() {
+        let log = Log::default();
+        let (outer, inner) = outer_and_inner();
+
+        let _outer = delegate(&outer, handler(logger(&log, "outer")));
+        let _inner = delegate(&inner, handler(logger(&log, "inner").stop_propagation()));
+
+        inner.click();
+        assert_eq!(vec!["inner"], *log.borrow());
+        outer.remove();
+    }
+
+    #[wasm_bindgen_test]
+    fn dropping_unregisters_the_handler() {
+        let log = Log::default();
+        let (outer, inner) = outer_and_inner();
+
+        let delegated = delegate(&inner, handler(logger(&log, "inner")));
+        let element_id = delegated.element_id;
+        drop(delegated);
+
+        inner.click();
+        assert!(log.borrow().is_empty());
+        assert!(DELEGATION.with_borrow(|delegation| !delegation.handlers.contains_key(&element_id)));
+        outer.remove();
+    }
+}
@@ -1,11 +1,20 @@
 use fnv::FnvHashMap;
-use kano::{Children, DiffProps, Props, View};
+use kano::{
+    attr::{Bind, FocusAttr},
+    Children, DiffProps, Props, View,
+};
 use kano_html::{
-    properties::{Property, PropertyValue},
+    properties::{ClassToggle, Property, PropertyValue, StyleProperty},
     HtmlAttribute, HtmlElement,
 };
 
-use crate::{web_cursor::WebCursor, Html5, Web};
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::{
+    focus::FocusBinding,
+    web_cursor::{get_property, Binding, HandlerSlot, WebCursor},
+    Html5, Web,
+};
 
 impl<A: Props<HtmlAttribute> + DiffProps<Web, Html5>, C: Children<Web, Html5>> View<Web, Html5>
     for HtmlElement<A, C>
@@ -38,9 +47,9 @@ impl<A: Props<HtmlAttribute> + DiffProps<Web, Html5>, C: Children<Web, Html5>> V
 impl<const N: usize> DiffProps<Web, Html5> for [Option<HtmlAttribute>; N] {
     /// The responsibility of the ConstState is to own the EventListeners
     /// and keep them active as long as the element is visible:
-    type ConstState = Vec<gloo::events::EventListener>;
+    type ConstState = Vec<Listener>;
 
-    type DiffState = (Self, FnvHashMap<usize, gloo::events::EventListener>);
+    type DiffState = (Self, FnvHashMap<usize, Listener>);
 
     fn init_const(self, cursor: &mut WebCursor) -> Self::ConstState {
         let mut listeners = vec![];
@@ -48,15 +57,30 @@ impl<const N: usize> DiffProps<Web, Html5> for [Option<HtmlAttribute>; N] {
         for prop in self.iter() {
             match prop {
                 Some(HtmlAttribute::Event(on_event)) => {
-                    listeners.push(cursor.on_event(on_event.clone()));
+                    listeners.push(Listener::Event(cursor.on_event(on_event.clone())));
+                }
+                Some(HtmlAttribute::Bind(bind)) => {
+                    listeners.push(Listener::Bind(cursor.bind(*bind)));
                 }
                 Some(HtmlAttribute::Attribute(property)) => {
                     set_html_attribute(cursor.get_element(), property);
                 }
+                Some(HtmlAttribute::Class(class)) => {
+                    toggle_class(cursor.get_element(), class);
+                }
+                Some(HtmlAttribute::Style(style)) => {
+                    set_style_property(cursor.get_element(), style);
+                }
+                Some(HtmlAttribute::Focus(focus)) => {
+                    if let Some(binding) = cursor.focus(*focus) {
+                        listeners.push(Listener::Focus(binding));
+                    }
+                }
                 _ => {}
             }
         }
 
+        reapply_directives(cursor.get_element(), &self);
         listeners
     }
 
@@ -66,23 +90,51 @@ impl<const N: usize> DiffProps<Web, Html5> for [Option<HtmlAttribute>; N] {
         for (index, prop) in self.iter().enumerate() {
             match prop {
                 Some(HtmlAttribute::Event(on_event)) => {
-                    listeners.insert(index, cursor.on_event(on_event.clone()));
+                    listeners.insert(index, Listener::Event(cursor.on_event(on_event.clone())));
+                }
+                Some(HtmlAttribute::Bind(bind)) => {
+                    listeners.insert(index, Listener::Bind(cursor.bind(*bind)));
                 }
                 Some(HtmlAttribute::Attribute(property)) => {
                     set_html_attribute(cursor.get_element(), property);
                 }
+                Some(HtmlAttribute::Class(class)) => {
+                    toggle_class(cursor.get_element(), class);
+                }
+                Some(HtmlAttribute::Style(style)) => {
+                    set_style_property(cursor.get_element(), style);
+                }
+                Some(HtmlAttribute::Focus(focus)) => {
+                    if let Some(binding) = cursor.focus(*focus) {
+                        listeners.insert(index, Listener::Focus(binding));
+                    }
+                }
                 _ => {}
             }
         }
 
+        reapply_directives(cursor.get_element(), &self);
         (self, listeners)
     }
 
     fn diff(self, (old_props, listeners): &mut Self::DiffState, cursor: &mut WebCursor) {
+        // Whether a whole `class` or `style` attribute was replaced
+        let mut replaced = false;
+        // Classes of `class:` directives that were renamed or removed
+        let mut removed_classes = vec![];
+
         for (index, (new, state)) in self.into_iter().zip(old_props.iter_mut()).enumerate() {
             match (new, &state) {
                 (Some(HtmlAttribute::Event(on_event)), _) => {
-                    listeners.insert(index, cursor.on_event(on_event.clone()));
+                    // Swap the handler of the existing listener when possible,
+                    // so the DOM listener isn't removed and added again
+                    let on_event = match listeners.get(&index) {
+                        Some(Listener::Event(slot)) => slot.swap(on_event),
+                        _ => Some(on_event),
+                    };
+                    if let Some(on_event) = on_event {
+                        listeners.insert(index, Listener::Event(cursor.on_event(on_event)));
+                    }
                 }
                 (None, Some(HtmlAttribute::Event(_))) => {
                     // Listener was weirdly deleted
@@ -91,27 +143,155 @@ impl<const N: usize> DiffProps<Web, Html5> for [Option<HtmlAttribute>; N] {
                 (Some(HtmlAttribute::Attribute(property)), Some(HtmlAttribute::Attribute(old))) => {
                     if &property != old {
                         set_html_attribute(cursor.get_element(), &property);
+                        replaced |= has_directives(&property);
                     }
                     *state = Some(HtmlAttribute::Attribute(property));
                 }
                 (Some(HtmlAttribute::Attribute(property)), None) => {
                     set_html_attribute(cursor.get_element(), &property);
+                    replaced |= has_directives(&property);
                     *state = Some(HtmlAttribute::Attribute(property));
                 }
+                (Some(HtmlAttribute::Bind(bind)), Some(HtmlAttribute::Bind(old)))
+                    if bind == *old =>
+                {
+                    if let Some(Listener::Bind(binding)) = listeners.get(&index) {
+                        binding.sync();
+                    }
+                }
+                (Some(HtmlAttribute::Bind(bind)), _) => {
+                    listeners.insert(index, Listener::Bind(cursor.bind(bind)));
+                    *state = Some(HtmlAttribute::Bind(bind));
+                }
+                (None, Some(HtmlAttribute::Bind(_))) => {
+                    listeners.remove(&index);
+                    *state = None;
+                }
+                (Some(HtmlAttribute::Class(class)), Some(HtmlAttribute::Class(old))) => {
+                    if &class != old {
+                        if class.name != old.name && old.enabled {
+                            removed_classes.push(old.name.clone());
+                        }
+                        toggle_class(cursor.get_element(), &class);
+                    }
+                    *state = Some(HtmlAttribute::Class(class));
+                }
+                (Some(HtmlAttribute::Class(class)), None) => {
+                    toggle_class(cursor.get_element(), &class);
+                    *state = Some(HtmlAttribute::Class(class));
+                }
+                (None, Some(HtmlAttribute::Class(old))) => {
+                    removed_classes.push(old.name.clone());
+                    *state = None;
+                }
+                (Some(HtmlAttribute::Style(style)), Some(HtmlAttribute::Style(old))) => {
+                    if &style != old {
+                        if style.name != old.name {
+                            remove_style_property(cursor.get_element(), &old.name);
+                        }
+                        set_style_property(cursor.get_element(), &style);
+                    }
+                    *state = Some(HtmlAttribute::Style(style));
+                }
+                (Some(HtmlAttribute::Style(style)), None) => {
+                    set_style_property(cursor.get_element(), &style);
+                    *state = Some(HtmlAttribute::Style(style));
+                }
+                (None, Some(HtmlAttribute::Style(old))) => {
+                    remove_style_property(cursor.get_element(), &old.name);
+                    *state = None;
+                }
+                (Some(HtmlAttribute::Focus(focus)), Some(HtmlAttribute::Focus(old)))
+                    if focus == *old => {}
+                (Some(HtmlAttribute::Focus(focus)), _) => {
+                    if let Some(HtmlAttribute::Focus(old)) = state {
+                        remove_focus(cursor.get_element(), old);
+                    }
+                    match cursor.focus(focus) {
+                        Some(binding) => listeners.insert(index, Listener::Focus(binding)),
+                        None => listeners.remove(&index),
+                    };
+                    *state = Some(HtmlAttribute::Focus(focus));
+                }
+                (None, Some(HtmlAttribute::Focus(old))) => {
+                    remove_focus(cursor.get_element(), old);
+                    listeners.remove(&index);
+                    *state = None;
+                }
                 (None, Some(HtmlAttribute::Attribute(prop))) => {
                     cursor
                         .get_element()
-                        .remove_attribute(prop.idl_name)
+                        .remove_attribute(attribute_name(prop))
                         .unwrap();
+                    replaced |= has_directives(prop);
+                    *state = None;
                 }
                 _ => kano::log("TODO: Set other attribute"),
             }
         }
+
+        for name in removed_classes {
+            if !has_class(old_props, &name) {
+                remove_class(cursor.get_element(), &name);
+            }
+        }
+        if replaced {
+            reapply_directives(cursor.get_element(), old_props);
+        }
+    }
+}
+
+/// Whether the `class` attribute or an enabled `class:` directive still applies the class.
+fn has_class(props: &[Option<HtmlAttribute>], name: &str) -> bool {
+    props.iter().any(|prop| match prop {
+        Some(HtmlAttribute::Class(class)) => class.enabled && class.name == name,
+        Some(HtmlAttribute::Attribute(property)) if attribute_name(property) == "class" => {
+            match &property.value {
+                PropertyValue::String(classes) => classes.split_whitespace().any(|c| c == name),
+                PropertyValue::SpaceSep(classes) => classes.iter().any(|c| c == name),
+                _ => false,
+            }
+        }
+        _ => false,
+    })
+}
+
+/// Whether setting the attribute overwrites what `class:` and `style:` directives applied.
+fn has_directives(property: &Property) -> bool {
+    matches!(attribute_name(property), "class" | "style")
+}
+
+/// Apply the `class:` and `style:` directives again, after the `class` or `style` attribute was set.
+fn reapply_directives(element: &web_sys::Element, props: &[Option<HtmlAttribute>]) {
+    for prop in props {
+        match prop {
+            Some(HtmlAttribute::Class(class)) if class.enabled => toggle_class(element, class),
+            Some(HtmlAttribute::Style(style)) => set_style_property(element, style),
+            _ => {}
+        }
+    }
+}
+
+/// An event listener owned by a property.
+pub enum Listener {
+    Event(HandlerSlot),
+    Bind(Binding),
+    Focus(FocusBinding),
+}
+
+/// The content attribute reflected by the IDL attribute of the property.
+fn attribute_name(property: &Property) -> &'static str {
+    match property.idl_name {
+        "className" => "class",
+        "acceptCharset" => "accept-charset",
+        "httpEquiv" => "http-equiv",
+        // The others only differ in case, and HTML attribute names are case-insensitive
+        name => name,
     }
 }
 
 fn set_html_attribute(element: &web_sys::Element, property: &Property) {
-    let name = property.idl_name;
+    let name = attribute_name(property);
     match &property.value {
         PropertyValue::String(string) => {
             element.set_attribute(name, string).unwrap();
@@ -140,3 +320,155 @@ fn set_html_attribute(element: &web_sys::Element, property: &Property) {
         },
     }
 }
+
+fn toggle_class(element: &web_sys::Element, class: &ClassToggle) {
+    element
+        .class_list()
+        .toggle_with_force(&class.name, class.enabled)
+        .unwrap();
+}
+
+fn remove_class(element: &web_sys::Element, name: &str) {
+    element.class_list().remove_1(name).unwrap();
+}
+
+/// Undo what a focus property applied to the element, besides its binding.
+fn remove_focus(element: &web_sys::Element, focus: &FocusAttr) {
+    if let FocusAttr::TabIndex(_) = focus {
+        element.remove_attribute("tabindex").unwrap();
+    }
+}
+
+/// The inline style declaration of the element, if it has one.
+///
+/// Both HTML and SVG elements have a `style` property.
+fn inline_style(element: &web_sys::Element) -> Option<web_sys::CssStyleDeclaration> {
+    js_sys::Reflect::get(element, &JsValue::from_str("style"))
+        .ok()?
+        .dyn_into()
+        .ok()
+}
+
+fn set_style_property(element: &web_sys::Element, style: &StyleProperty) {
+    if let Some(declaration) = inline_style(element) {
+        declaration.set_property(&style.name, &style.value).unwrap();
+    }
+}
+
+fn remove_style_property(element: &web_sys::Element, name: &str) {
+    if let Some(declaration) = inline_style(element) {
+        declaration.remove_property(name).unwrap();
+    }
+}
+
+/// Update a bound property of the element, if it differs from the bound state.
+///
+/// The element is left alone when it is in sync, so that e.g. the caret position
+/// of a text input is not disturbed by the state update caused by its own input.
+pub(crate) fn set_bound_property(element: &web_sys::Element, bind: Bind) {
+    let (name, value) = match bind {
+        Bind::Value(state) => ("value", JsValue::from_str(&state.get())),
+        Bind::Checked(state) => ("checked", JsValue::from_bool(state.get())),
+    };
+
+    if get_property(element, name) != value {
+        js_sys::Reflect::set(element, &JsValue::from_str(name), &value).unwrap();
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use wasm_bindgen_test::*;
+
+    use super::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn props(class: &'static str, style: &'static str) -> [Option<HtmlAttribute>; 4] {
+        [
+            Some(HtmlAttribute::Class(ClassToggle {
+                name: "active".into(),
+                enabled: true,
+            })),
+            Some(HtmlAttribute::Style(StyleProperty {
+                name: "color".into(),
+                value: "red".into(),
+            })),
+            Some(HtmlAttribute::Attribute(Property::new(
+                "className",
+                PropertyValue::String(class.into()),
+            ))),
+            Some(HtmlAttribute::Attribute(Property::new(
+                "style",
+                PropertyValue::String(style.into()),
+            ))),
+        ]
+    }
+
+    #[wasm_bindgen_test]
+    fn class_and_style_attributes_keep_directives() {
+        let mut cursor = WebCursor::new_detached();
+        let _ = cursor.element("div");
+
+        let mut state = props("a", "margin: 1px").init_diff(&mut cursor);
+        let element = cursor.get_element().clone();
+        assert!(element.class_list().contains("a"));
+        assert!(element.class_list().contains("active"));
+
+        props("b", "margin: 2px").diff(&mut state, &mut cursor);
+        assert!(!element.class_list().contains("a"));
+        assert!(element.class_list().contains("b"));
+        assert!(element.class_list().contains("active"));
+
+        let style = element.get_attribute("style").unwrap();
+        assert!(style.contains("margin: 2px"), "{style}");
+        assert!(style.contains("color: red"), "{style}");
+    }
+
+    #[wasm_bindgen_test]
+    fn removed_directives_keep_classes_of_the_class_attribute() {
+        let mut cursor = WebCursor::new_detached();
+        let _ = cursor.element("div");
+
+        let class_attribute = || {
+            Some(HtmlAttribute::Attribute(Property::new(
+                "className",
+                PropertyValue::String("a active".into()),
+            )))
+        };
+        let directive = |name: &'static str| {
+            Some(HtmlAttribute::Class(ClassToggle {
+                name: name.into(),
+                enabled: true,
+            }))
+        };
+
+        let mut state = [directive("active"), class_attribute()].init_diff(&mut cursor);
+        let element = cursor.get_element().clone();
+
+        [directive("b"), class_attribute()].diff(&mut state, &mut cursor);
+        assert!(element.class_list().contains("active"));
+        assert!(element.class_list().contains("b"));
+
+        [None, class_attribute()].diff(&mut state, &mut cursor);
+        assert!(!element.class_list().contains("b"));
+        assert!(element.class_list().contains("a"));
+        assert!(element.class_list().contains("active"));
+    }
+
+    #[wasm_bindgen_test]
+    fn removing_tabindex_removes_the_attribute() {
+        let mut cursor = WebCursor::new_detached();
+        let _ = cursor.element("div");
+
+        let mut state = [Some(HtmlAttribute::Focus(FocusAttr::TabIndex(2)))].init_diff(&mut cursor);
+        let element = cursor.get_element().clone();
+        assert_eq!(Some("2".to_string()), element.get_attribute("tabindex"));
+
+        [Some(HtmlAttribute::Focus(FocusAttr::TabIndex(-1)))].diff(&mut state, &mut cursor);
+        assert_eq!(Some("-1".to_string()), element.get_attribute("tabindex"));
+
+        [None::<HtmlAttribute>].diff(&mut state, &mut cursor);
+        assert_eq!(None, element.get_attribute("tabindex"));
+    }
+}
@@ -0,0 +1,174 @@
+//! Translation of DOM events into Kano's platform-agnostic event payloads.
+
+use gloo::events::{EventListener, EventListenerOptions};
+use kano::{
+    attr::Event,
+    event::{
+        AnyEvent, ClickEvent, FocusEvent, InputEvent, Key, KeyEvent, Modifiers, MouseEvent,
+        PointerEvent, ScrollEvent, SubmitEvent,
+    },
+};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::window;
+
+use crate::{document, web_cursor::get_property};
+
+/// Translate a DOM event received for the Kano event type `kind`.
+pub(crate) fn any_event(kind: Event, event: &web_sys::Event) -> AnyEvent {
+    match kind {
+        Event::Click => {
+            let MouseEvent { x, y, modifiers } = mouse_event(event);
+            AnyEvent::Click(ClickEvent { x, y, modifiers })
+        }
+        Event::MouseOver | Event::MouseEnter | Event::MouseLeave => {
+            AnyEvent::Mouse(mouse_event(event))
+        }
+        Event::PointerDown | Event::PointerUp | Event::PointerMove => {
+            let MouseEvent { x, y, modifiers } = mouse_event(event);
+            AnyEvent::Pointer(PointerEvent {
+                x,
+                y,
+                pointer_id: event
+                    .dyn_ref::<web_sys::PointerEvent>()
+                    .map(|pointer| pointer.pointer_id())
+                    .unwrap_or_default(),
+                modifiers,
+            })
+        }
+        Event::KeyDown | Event::KeyUp => AnyEvent::Key(key_event(event)),
+        Event::Input | Event::Change => AnyEvent::Input(InputEvent {
+            value: event
+                .target()
+                .and_then(|target| get_property(&target, "value").as_string())
+                .unwrap_or_default(),
+        }),
+        Event::Submit => AnyEvent::Submit(SubmitEvent),
+        Event::Focus | Event::Blur => AnyEvent::Focus(FocusEvent),
+        Event::Scroll => {
+            let element = event
+                .target()
+                .and_then(|target| target.dyn_into::<web_sys::Element>().ok());
+            AnyEvent::Scroll(ScrollEvent {
+                left: element
+                    .as_ref()
+                    .map(|el| el.scroll_left())
+                    .unwrap_or_default(),
+                top: element
+                    .as_ref()
+                    .map(|el| el.scroll_top())
+                    .unwrap_or_default(),
+            })
+        }
+    }
+}
+
+/// Feed key presses on the document into [kano::keymap].
+///
+/// Plain key presses in editable elements are text entry, and are left alone.
+pub(crate) fn shortcut_listener() -> EventListener {
+    EventListener::new_with_options(
+        &document(),
+        "keydown",
+        EventListenerOptions::enable_prevent_default(),
+        |event| {
+            let key_event = key_event(event);
+            if !key_event.modifiers.is_command() && event.target().is_some_and(|t| is_editable(&t))
+            {
+                return;
+            }
+
+            if kano::keymap::dispatch(&key_event) {
+                event.prevent_default();
+            }
+        },
+    )
+}
+
+/// Keep [kano::viewport] up to date with the size of the window.
+pub(crate) fn viewport_listener() -> EventListener {
+    set_viewport();
+    EventListener::new(&window().unwrap(), "resize", |_| set_viewport())
+}
+
+fn set_viewport() {
+    let window = window().unwrap();
+    let size = |value: Result<JsValue, JsValue>| {
+        value.ok().and_then(|value| value.as_f64()).unwrap_or(0.0) as u32
+    };
+
+    kano::viewport::set_viewport(kano::viewport::Viewport {
+        width: size(window.inner_width()),
+        height: size(window.inner_height()),
+    });
+}
+
+fn is_editable(target: &web_sys::EventTarget) -> bool {
+    let Some(element) = target.dyn_ref::<web_sys::HtmlElement>() else {
+        return false;
+    };
+
+    element.is_content_editable()
+        || matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+}
+
+fn mouse_event(event: &web_sys::Event) -> MouseEvent {
+    event
+        .dyn_ref::<web_sys::MouseEvent>()
+        .map(|mouse| MouseEvent {
+            x: mouse.client_x(),
+            y: mouse.client_y(),
+            modifiers: Modifiers {
+                shift: mouse.shift_key(),
+                ctrl: mouse.ctrl_key(),
+                alt: mouse.alt_key(),
+                meta: mouse.meta_key(),
+            },
+        })
+        .unwrap_or_default()
+}
+
+fn key_event(event: &web_sys::Event) -> KeyEvent {
+    let Some(keyboard) = event.dyn_ref::<web_sys::KeyboardEvent>() else {
+        return KeyEvent {
+            key: Key::Unidentified,
+            modifiers: Modifiers::default(),
+        };
+    };
+
+    KeyEvent {
+        key: key(&keyboard.key()),
+        modifiers: Modifiers {
+            shift: keyboard.shift_key(),
+            ctrl: keyboard.ctrl_key(),
+            alt: keyboard.alt_key(),
+            meta: keyboard.meta_key(),
+        },
+    }
+}
+
+/// Translate the `key` property of a `KeyboardEvent`.
+fn key(key: &str) -> Key {
+    match key {
+        "Enter" => Key::Enter,
+        "Escape" => Key::Escape,
+        "Backspace" => Key::Backspace,
+        "Delete" => Key::Delete,
+        "Tab" => Key::Tab,
+        "ArrowLeft" => Key::Left,
+        "ArrowRight" => Key::Right,
+        "ArrowUp" => Key::Up,
+        "ArrowDown" => Key::Down,
+        "Home" => Key::Home,
+        "End" => Key::End,
+        "PageUp" => Key::PageUp,
+        "PageDown" => Key::PageDown,
+        other => {
+            let mut chars = other.chars();
+            match (chars.next(), chars.next()) {
+                (Some(char), None) => Key::Char(char),
+                (Some('F'), Some(_)) => other[1..].parse().map(Key::F).unwrap_or(Key::Unidentified),
+                _ => Key::Unidentified,
+            }
+        }
+    }
+}
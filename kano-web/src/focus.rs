@@ -0,0 +1,139 @@
+//! Focus management of DOM elements.
+
+use std::rc::Rc;
+
+use gloo::events::{EventListener, EventListenerOptions};
+use kano::{
+    attr::FocusAttr,
+    focus::{self, FocusRef, FocusTarget, Registration},
+};
+use wasm_bindgen::JsCast;
+use web_sys::HtmlElement;
+
+use crate::document;
+
+/// Elements that can receive focus from the keyboard.
+const FOCUSABLE_SELECTOR: &str = "a[href], button:not([disabled]), input:not([disabled]), \
+    select:not([disabled]), textarea:not([disabled]), [tabindex]:not([tabindex='-1'])";
+
+/// Keeps a focus property of an element active.
+pub enum FocusBinding {
+    Ref {
+        _registration: Registration,
+        _focus_listener: EventListener,
+        _blur_listener: EventListener,
+    },
+    Trap {
+        _keydown_listener: EventListener,
+    },
+}
+
+struct ElementFocusTarget(HtmlElement);
+
+impl FocusTarget for ElementFocusTarget {
+    fn focus(&self) {
+        let _ = self.0.focus();
+    }
+
+    fn blur(&self) {
+        let _ = self.0.blur();
+    }
+}
+
+/// Apply a focus property to an element.
+///
+/// Returns a binding that has to be kept alive for as long as the property applies.
+pub(crate) fn bind_focus(element: &web_sys::Element, focus: FocusAttr) -> Option<FocusBinding> {
+    let element: HtmlElement = element.clone().dyn_into().ok()?;
+
+    match focus {
+        FocusAttr::Ref(focus_ref) => Some(bind_focus_ref(element, focus_ref)),
+        FocusAttr::Autofocus(true) => {
+            // The element is not attached to the document until rendering is done
+            wasm_bindgen_futures::spawn_local(async move {
+                let _ = element.focus();
+            });
+            None
+        }
+        FocusAttr::Trap(true) => Some(trap_focus(element)),
+        FocusAttr::TabIndex(index) => {
+            element.set_tab_index(index);
+            None
+        }
+        FocusAttr::Autofocus(false) | FocusAttr::Trap(false) => None,
+    }
+}
+
+fn bind_focus_ref(element: HtmlElement, focus_ref: FocusRef) -> FocusBinding {
+    focus_ref.set_focused(is_active(&element));
+
+    FocusBinding::Ref {
+        _focus_listener: EventListener::new(&element, "focus", move |_| {
+            focus_ref.set_focused(true);
+        }),
+        _blur_listener: EventListener::new(&element, "blur", move |_| {
+            focus_ref.set_focused(false);
+        }),
+        _registration: focus::register(focus_ref, Rc::new(ElementFocusTarget(element))),
+    }
+}
+
+/// Move focus into the element, and keep Tab and Shift+Tab cycling among its descendants.
+fn trap_focus(element: HtmlElement) -> FocusBinding {
+    wasm_bindgen_futures::spawn_local({
+        let element = element.clone();
+        async move {
+            if !element.contains(document().active_element().as_deref()) {
+                if let Some(first) = focusables(&element).first() {
+                    let _ = first.focus();
+                }
+            }
+        }
+    });
+
+    FocusBinding::Trap {
+        _keydown_listener: EventListener::new_with_options(
+            &element.clone(),
+            "keydown",
+            EventListenerOptions::enable_prevent_default(),
+            move |event| {
+                let Some(keyboard) = event.dyn_ref::<web_sys::KeyboardEvent>() else {
+                    return;
+                };
+                if keyboard.key() != "Tab" {
+                    return;
+                }
+
+                let focusables = focusables(&element);
+                let (Some(first), Some(last)) = (focusables.first(), focusables.last()) else {
+                    return;
+                };
+                let wrap_to = if keyboard.shift_key() {
+                    is_active(first).then_some(last)
+                } else {
+                    is_active(last).then_some(first)
+                };
+
+                if let Some(wrap_to) = wrap_to {
+                    event.prevent_default();
+                    let _ = wrap_to.focus();
+                }
+            },
+        ),
+    }
+}
+
+fn is_active(element: &web_sys::Element) -> bool {
+    document().active_element().as_ref() == Some(element)
+}
+
+fn focusables(element: &HtmlElement) -> Vec<HtmlElement> {
+    let Ok(node_list) = element.query_selector_all(FOCUSABLE_SELECTOR) else {
+        return vec![];
+    };
+
+    (0..node_list.length())
+        .filter_map(|index| node_list.item(index))
+        .filter_map(|node| node.dyn_into().ok())
+        .collect()
+}
@@ -1,7 +1,7 @@
 //! Kano is a work-in-progress GUI application framework written for and in Rust.
 #![allow(non_snake_case, non_upper_case_globals)]
 
-use std::rc::Rc;
+use std::{cell::RefCell, rc::Rc};
 
 use anyhow::anyhow;
 use futures::{SinkExt, StreamExt};
@@ -9,13 +9,17 @@ use gloo::events::EventListener;
 use kano::{
     markup::Markup,
     platform::{Platform, PlatformContext, PlatformInit},
+    root::Root,
 };
 use kano_svg::Svg1_1;
 use wasm_bindgen::prelude::*;
 use web_cursor::{Position, WebCursor};
 use web_sys::{window, Document};
 
+mod delegation;
 mod diff;
+mod event;
+mod focus;
 mod web_cursor;
 
 #[cfg(feature = "web-component")]
@@ -37,6 +41,11 @@ mod js {
 
 pub struct Web {}
 
+thread_local! {
+    /// The root of the running app, which lives as long as the page.
+    static ROOT: RefCell<Option<Root<Web>>> = const { RefCell::new(None) };
+}
+
 impl Markup<Web> for Html5 {
     type Cursor = WebCursor;
 }
@@ -47,6 +56,7 @@ impl Markup<Web> for Svg1_1 {
 
 impl Platform for Web {
     type Markup = Html5;
+    type Config = ();
 
     fn init(init: PlatformInit) -> PlatformContext {
         console_error_panic_hook::set_once();
@@ -55,6 +65,11 @@ impl Platform for Web {
         wasm_bindgen_futures::spawn_local(async move {
             loop {
                 if let Some(()) = dispatch_rx.next().await {
+                    ROOT.with_borrow_mut(|root| {
+                        if let Some(root) = root {
+                            root.apply_replacement();
+                        }
+                    });
                     (init.signal_dispatch)();
                 } else {
                     panic!("signal connection lost");
@@ -83,22 +98,29 @@ impl Platform for Web {
         }
     }
 
-    fn run(view: impl kano::View<Self, Html5>, _context: PlatformContext) -> anyhow::Result<()> {
-        let mut cursor = WebCursor::new_detached();
-        let state = view.init_const(&mut cursor);
+    fn run(
+        view: impl kano::View<Self, Html5> + 'static,
+        _context: PlatformContext,
+        _config: (),
+    ) -> anyhow::Result<()> {
+        // The first render already sees the size of the window
+        let viewport_listener = event::viewport_listener();
+        let root = Root::<Web>::new(view, WebCursor::new_detached());
 
-        let Position::Node(node) = cursor.position else {
+        let Position::Node(node) = &root.cursor().position else {
             return Err(anyhow!("No node rendered"));
         };
 
         document()
             .body()
             .unwrap()
-            .append_child(&node)
+            .append_child(node)
             .map_err(|e| anyhow!("{e:?}"))?;
 
-        // Need to keep the initial state around, it keeps EventListeners alive
-        std::mem::forget(state);
+        // The root state keeps EventListeners alive
+        ROOT.set(Some(root));
+        std::mem::forget(event::shortcut_listener());
+        std::mem::forget(viewport_listener);
         Ok(())
     }
 
@@ -1,14 +1,24 @@
-use gloo::events::EventListener;
+use std::{cell::RefCell, rc::Rc};
+
+use gloo::events::{EventListener, EventListenerOptions, EventListenerPhase};
 use js_sys::wasm_bindgen::*;
 use kano_svg::SvgMarkup;
 use web_sys::{Element, EventTarget};
 
 use kano::{
-    attr::{Event, On},
+    attr::{Bind, Event, FocusAttr, On, Spent},
+    effect::Effect,
     markup::NestMarkup,
 };
 
-use crate::{document, Html5, Svg1_1, Web};
+use crate::{
+    delegation::{delegate, should_delegate, Delegated},
+    diff::set_bound_property,
+    document,
+    event::any_event,
+    focus::{bind_focus, FocusBinding},
+    Html5, Svg1_1, Web,
+};
 
 #[derive(Clone, Debug)]
 pub struct WebCursor {
@@ -37,24 +47,99 @@ impl WebCursor {
         element.into()
     }
 
-    pub fn on_event(&mut self, on_event: On<Event>) -> EventListener {
+    pub fn on_event(&mut self, on_event: On<Event>) -> HandlerSlot {
         match &mut self.position {
             Position::Node(element) => {
                 let event_target: &EventTarget = element.dyn_ref().unwrap();
-                let event_type = match on_event.event() {
-                    Event::Click => "click",
-                    Event::MouseOver => "mouseover",
-                };
+                let event_type = on_event.event().name();
+                let listener_options = listener_options(&on_event);
+                let handler = Rc::new(SlotHandler {
+                    on_event: RefCell::new(on_event),
+                    spent: Spent::default(),
+                });
+
+                if should_delegate(&handler.on_event.borrow()) {
+                    let delegated = delegate(element.dyn_ref().unwrap(), handler.clone());
+                    return HandlerSlot {
+                        handler,
+                        _listener: SlotListener::Delegated(delegated),
+                    };
+                }
 
-                EventListener::new(event_target, event_type, move |_| {
-                    on_event.invoke();
-                })
+                let listener =
+                    EventListener::new_with_options(event_target, event_type, listener_options, {
+                        let handler = handler.clone();
+                        move |event| {
+                            // Don't hold the borrow while the handler runs
+                            let on_event = handler.on_event.borrow().clone();
+                            let options = on_event.options();
+
+                            if on_event.invoke(&any_event(*on_event.event(), event), &handler.spent)
+                            {
+                                if options.prevent_default {
+                                    event.prevent_default();
+                                }
+                                if options.stop_propagation {
+                                    event.stop_propagation();
+                                }
+                            }
+                        }
+                    });
+
+                HandlerSlot {
+                    handler,
+                    _listener: SlotListener::Direct(listener),
+                }
             }
             Position::Detached => panic!(),
             Position::AfterLastChild(_) | Position::EndOfShadowRoot(_) => panic!(),
         }
     }
 
+    /// Set up a two-way binding on the current element:
+    /// The bound property is set from the state whenever it changes, and user input is written back into it.
+    pub fn bind(&mut self, bind: Bind) -> Binding {
+        let element = self.get_element();
+        let sync = Effect::new({
+            let element = element.clone();
+            move || set_bound_property(&element, bind)
+        });
+
+        let event_type = match bind {
+            Bind::Value(_) => "input",
+            Bind::Checked(_) => "change",
+        };
+
+        let listener = EventListener::new(element, event_type, move |event| {
+            let Some(target) = event.target() else {
+                return;
+            };
+
+            match bind {
+                Bind::Value(state) => {
+                    if let Some(value) = get_property(&target, "value").as_string() {
+                        state.set(value);
+                    }
+                }
+                Bind::Checked(state) => {
+                    if let Some(checked) = get_property(&target, "checked").as_bool() {
+                        state.set(checked);
+                    }
+                }
+            }
+        });
+
+        Binding {
+            sync,
+            _listener: listener,
+        }
+    }
+
+    /// Apply a focus property to the current element.
+    pub fn focus(&mut self, focus: FocusAttr) -> Option<FocusBinding> {
+        bind_focus(self.get_element(), focus)
+    }
+
     pub fn get_element(&self) -> &web_sys::Element {
         match &self.position {
             Position::Node(node) => node.dyn_ref().unwrap(),
@@ -245,6 +330,79 @@ impl SvgMarkup<Web> for Svg1_1 {
     }
 }
 
+/// A handler which can be swapped while its listener stays registered.
+pub(crate) type SharedHandler = Rc<SlotHandler>;
+
+pub(crate) struct SlotHandler {
+    pub on_event: RefCell<On<Event>>,
+    /// Belongs to the slot and not to the handler, so that a swapped in handler stays spent.
+    pub spent: Spent,
+}
+
+/// An event listener which is registered once,
+/// and dispatches to a handler that can be swapped when diffing.
+pub struct HandlerSlot {
+    handler: SharedHandler,
+    _listener: SlotListener,
+}
+
+/// Keeps the handler of a [HandlerSlot] reachable from the DOM.
+#[allow(dead_code)]
+enum SlotListener {
+    Direct(EventListener),
+    Delegated(Delegated),
+}
+
+impl HandlerSlot {
+    /// Replace the handler, keeping the registered listener.
+    ///
+    /// A handler for another event type, with other listener options or delegated differently
+    /// can't reuse the listener, and is given back.
+    pub fn swap(&self, on_event: On<Event>) -> Option<On<Event>> {
+        let mut handler = self.handler.on_event.borrow_mut();
+        if handler.event() == on_event.event()
+            && listener_options(&handler) == listener_options(&on_event)
+            && should_delegate(&handler) == should_delegate(&on_event)
+        {
+            *handler = on_event;
+            None
+        } else {
+            Some(on_event)
+        }
+    }
+}
+
+/// A two-way binding of an element to a state.
+pub struct Binding {
+    sync: Effect,
+    _listener: EventListener,
+}
+
+impl Binding {
+    /// Set the bound property from the state, e.g. when the element is diffed.
+    pub fn sync(&self) {
+        self.sync.run();
+    }
+}
+
+/// The listener options a handler is registered with.
+fn listener_options(on_event: &On<Event>) -> EventListenerOptions {
+    let options = on_event.options();
+    EventListenerOptions {
+        phase: if options.capture {
+            EventListenerPhase::Capture
+        } else {
+            EventListenerPhase::Bubble
+        },
+        // preventDefault() is ignored in passive listeners
+        passive: !options.prevent_default,
+    }
+}
+
+pub(crate) fn get_property(target: &JsValue, name: &str) -> JsValue {
+    js_sys::Reflect::get(target, &JsValue::from_str(name)).unwrap_or(JsValue::UNDEFINED)
+}
+
 impl WebCursor {
     fn append_node(&mut self, appendee: &web_sys::Node) {
         // log(&format!("append at Cursor: {self:?}"));
@@ -271,3 +429,43 @@ impl WebCursor {
         }
     }
 }
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use std::cell::Cell;
+
+    use kano::property::on;
+    use wasm_bindgen_test::*;
+
+    use super::*;
+    use crate::document;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn counter(clicks: &Rc<Cell<u32>>) -> On<Event> {
+        let clicks = clicks.clone();
+        on::click(move |_| clicks.set(clicks.get() + 1)).into()
+    }
+
+    #[wasm_bindgen_test]
+    fn once_handler_stays_spent_when_swapped() {
+        let (first, second) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+
+        let mut cursor = WebCursor::new_detached();
+        let button: web_sys::HtmlElement = cursor.element("button").unchecked_into();
+        // Delegated handlers only see events in the document
+        document().body().unwrap().append_child(&button).unwrap();
+
+        let slot = cursor.on_event(counter(&first).once());
+        button.click();
+        button.click();
+        assert_eq!(1, first.get());
+
+        // A re-render swaps in the handler of the new render
+        assert!(slot.swap(counter(&second).once()).is_none());
+        button.click();
+        assert_eq!(0, second.get());
+
+        button.remove();
+    }
+}
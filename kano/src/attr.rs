@@ -1,55 +1,309 @@
-use std::{borrow::Cow, fmt::Debug, rc::Rc};
+use std::{borrow::Cow, cell::Cell, fmt::Debug, rc::Rc};
+
+use crate::{
+    event::{
+        AnyEvent, ClickEvent, FocusEvent, InputEvent, KeyEvent, MouseEvent, PointerEvent,
+        ScrollEvent, SubmitEvent,
+    },
+    focus::FocusRef,
+    reactive::State,
+};
 
 /// This attributes represents the target of a hyperlink.
 #[derive(Clone, Debug)]
 pub struct To(pub Cow<'static, str>);
 
-#[derive(Clone, Copy, Debug)]
-pub struct Click;
+/// An event type, which determines the payload passed to its handlers.
+pub trait EventType {
+    type Payload;
+}
 
-#[derive(Clone, Copy, Debug)]
-pub struct MouseOver;
+macro_rules! events {
+    ($(($ident:ident, $name:literal, $payload:ident, $any:ident)),+ $(,)?) => {
+        $(
+            #[doc = concat!("The `", $name, "` event.")]
+            #[derive(Clone, Copy, Debug)]
+            pub struct $ident;
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-pub enum Event {
-    Click,
-    MouseOver,
+            impl EventType for $ident {
+                type Payload = $payload;
+            }
+
+            impl From<On<$ident>> for On<Event> {
+                fn from(value: On<$ident>) -> Self {
+                    let func = value.func;
+                    Self {
+                        event: Event::$ident,
+                        func: Rc::new(move |payload| {
+                            if let AnyEvent::$any(payload) = payload {
+                                func(payload);
+                            }
+                        }),
+                        options: value.options,
+                    }
+                }
+            }
+        )+
+
+        /// Any event type.
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+        pub enum Event {
+            $($ident),+
+        }
+
+        impl Event {
+            /// The lowercase name of the event, as used by the DOM.
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $(Self::$ident => $name),+
+                }
+            }
+        }
+    };
+}
+
+events!(
+    (Click, "click", ClickEvent, Click),
+    (MouseOver, "mouseover", MouseEvent, Mouse),
+    (MouseEnter, "mouseenter", MouseEvent, Mouse),
+    (MouseLeave, "mouseleave", MouseEvent, Mouse),
+    (PointerDown, "pointerdown", PointerEvent, Pointer),
+    (PointerUp, "pointerup", PointerEvent, Pointer),
+    (PointerMove, "pointermove", PointerEvent, Pointer),
+    (KeyDown, "keydown", KeyEvent, Key),
+    (KeyUp, "keyup", KeyEvent, Key),
+    (Input, "input", InputEvent, Input),
+    (Change, "change", InputEvent, Input),
+    (Submit, "submit", SubmitEvent, Submit),
+    (Focus, "focus", FocusEvent, Focus),
+    (Blur, "blur", FocusEvent, Focus),
+    (Scroll, "scroll", ScrollEvent, Scroll),
+);
+
+impl Event {
+    /// Whether the event propagates from its target to the ancestors of the target.
+    pub const fn bubbles(self) -> bool {
+        !matches!(
+            self,
+            Self::MouseEnter | Self::MouseLeave | Self::Focus | Self::Blur | Self::Scroll
+        )
+    }
+}
+
+/// Type-erased handlers receive any payload.
+impl EventType for Event {
+    type Payload = AnyEvent;
 }
 
-#[derive(Clone)]
-pub struct On<E> {
+/// A shared event handler function.
+pub type Handler<E> = Rc<dyn Fn(&<E as EventType>::Payload)>;
+
+/// Options changing how an event is dispatched to a handler.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct EventOptions {
+    /// Prevent the platform's default action for the event.
+    pub prevent_default: bool,
+    /// Stop the event from propagating to further elements.
+    pub stop_propagation: bool,
+    /// Invoke the handler at most once.
+    pub once: bool,
+    /// Invoke the handler while the event travels down towards its target,
+    /// before any handlers on the target itself.
+    pub capture: bool,
+    /// On the web, dispatch through a single listener per event type on the document,
+    /// instead of a listener for each element. For handlers repeated in big lists.
+    pub delegate: bool,
+}
+
+pub struct On<E: EventType> {
     event: E,
-    func: Rc<dyn Fn()>,
+    func: Handler<E>,
+    options: EventOptions,
+}
+
+impl<E: EventType + Clone> Clone for On<E> {
+    fn clone(&self) -> Self {
+        Self {
+            event: self.event.clone(),
+            func: self.func.clone(),
+            options: self.options,
+        }
+    }
 }
 
-impl<E: Debug> Debug for On<E> {
+impl<E: EventType + Debug> Debug for On<E> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("On").field("event", &self.event).finish()
+        f.debug_struct("On")
+            .field("event", &self.event)
+            .field("options", &self.options)
+            .finish()
     }
 }
 
-impl<E> On<E> {
-    pub(crate) fn new(event: E, func: Rc<dyn Fn()>) -> Self {
-        Self { event, func }
+impl<E: EventType> On<E> {
+    pub(crate) fn new(event: E, func: Handler<E>) -> Self {
+        Self {
+            event,
+            func,
+            options: EventOptions::default(),
+        }
     }
 
     pub fn event(&self) -> &E {
         &self.event
     }
 
-    pub fn invoke(&self) {
-        (self.func)();
+    pub fn options(&self) -> EventOptions {
+        self.options
+    }
+
+    /// Prevent the platform's default action for the event, e.g. reloading the page on form submission.
+    pub fn prevent_default(mut self) -> Self {
+        self.options.prevent_default = true;
+        self
+    }
+
+    /// Stop the event from propagating to the handlers of ancestor elements.
+    pub fn stop_propagation(mut self) -> Self {
+        self.options.stop_propagation = true;
+        self
+    }
+
+    /// Invoke the handler at most once while it's attached, also across re-renders replacing it.
+    pub fn once(mut self) -> Self {
+        self.options.once = true;
+        self
     }
+
+    /// Invoke the handler in the capturing phase, before handlers of descendant elements.
+    pub fn capture(mut self) -> Self {
+        self.options.capture = true;
+        self
+    }
+
+    /// Dispatch through a listener shared with other handlers, see [EventOptions::delegate].
+    pub fn delegate(mut self) -> Self {
+        self.options.delegate = true;
+        self
+    }
+
+    /// Invoke the handler.
+    ///
+    /// Returns `false` if the handler was not invoked because it is a [Self::once] handler,
+    /// and `spent` tells that it was already invoked.
+    pub fn invoke(&self, payload: &E::Payload, spent: &Spent) -> bool {
+        if self.options.once && spent.0.replace(true) {
+            return false;
+        }
+
+        (self.func)(payload);
+        true
+    }
+}
+
+/// Whether a [On::once] handler was invoked.
+///
+/// The platform keeps one for each handler it attaches to a node, outliving the [On] values
+/// of each render which replace the handler, so that the handler stays spent.
+#[derive(Clone, Default, Debug)]
+pub struct Spent(Rc<Cell<bool>>);
+
+/// A two-way binding between a property of an input element and a [State].
+///
+/// The property is kept in sync with the state,
+/// and user input is written back into the state.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Bind {
+    /// The value of a text input, text area or select.
+    Value(State<String>),
+    /// The checked state of a checkbox.
+    Checked(State<bool>),
 }
 
-impl From<On<Click>> for On<Event> {
-    fn from(value: On<Click>) -> Self {
-        Self::new(Event::Click, value.func)
+impl Debug for Bind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Value(_) => f.debug_tuple("Value").finish(),
+            Self::Checked(_) => f.debug_tuple("Checked").finish(),
+        }
     }
 }
 
-impl From<On<MouseOver>> for On<Event> {
-    fn from(value: On<MouseOver>) -> Self {
-        Self::new(Event::MouseOver, value.func)
+/// Presentation of a text input.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum InputAttr {
+    /// Text shown while the input is empty.
+    Placeholder(Cow<'static, str>),
+    /// Mask the value, for entering passwords.
+    Password(bool),
+}
+
+/// Focus management of an element, see [crate::focus].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FocusAttr {
+    /// Attach a [FocusRef] to the element.
+    Ref(FocusRef),
+    /// Focus the element when it's first shown.
+    Autofocus(bool),
+    /// Keep focus within the element and its descendants.
+    Trap(bool),
+    /// The position of the element in sequential (Tab) focus navigation.
+    ///
+    /// Like the DOM `tabindex`: Positive values come first in ascending order, then `0` in document order.
+    /// Negative values can only be focused programmatically.
+    TabIndex(i32),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        event::{AnyEvent, FocusEvent, Key, KeyEvent, Modifiers},
+        property::on,
+    };
+
+    use super::*;
+
+    #[test]
+    fn erased_handler_only_receives_its_own_payload() {
+        let pressed = Rc::new(Cell::new(None));
+        let handler: On<Event> = on::keydown({
+            let pressed = pressed.clone();
+            move |event| pressed.set(Some(event.key))
+        })
+        .into();
+
+        assert_eq!(Event::KeyDown, *handler.event());
+        assert_eq!("keydown", handler.event().name());
+
+        handler.invoke(&AnyEvent::Focus(FocusEvent), &Spent::default());
+        assert_eq!(None, pressed.get());
+
+        handler.invoke(
+            &AnyEvent::Key(KeyEvent {
+                key: Key::Enter,
+                modifiers: Modifiers::default(),
+            }),
+            &Spent::default(),
+        );
+        assert_eq!(Some(Key::Enter), pressed.get());
+    }
+
+    #[test]
+    fn once_handler_is_spent_after_first_invocation() {
+        let clicks = Rc::new(Cell::new(0));
+        let handler: On<Event> = on::click({
+            let clicks = clicks.clone();
+            move |_| clicks.set(clicks.get() + 1)
+        })
+        .once()
+        .into();
+
+        let spent = Spent::default();
+        assert!(handler.options().once);
+        assert!(handler.invoke(&AnyEvent::Click(Default::default()), &spent));
+        assert!(!handler
+            .clone()
+            .invoke(&AnyEvent::Click(Default::default()), &spent));
+        assert_eq!(1, clicks.get());
     }
 }
@@ -0,0 +1,105 @@
+//! Effects keep platform output in sync with state outside of views.
+
+use std::rc::Rc;
+
+use crate::{registry::REGISTRY, view_id::ViewId};
+
+/// A function that runs again whenever a signal it read has been sent, for as long as the effect is kept.
+///
+/// Platforms use effects to update output that is not rendered by a view,
+/// e.g. the DOM value of an element with a two-way binding.
+pub struct Effect {
+    view_id: ViewId,
+    func: Rc<dyn Fn()>,
+}
+
+impl Effect {
+    /// Run `func`, and again after every change to the signals it read.
+    pub fn new(func: impl Fn() + 'static) -> Self {
+        let func: Rc<dyn Fn()> = Rc::new(func);
+        let weak_func = Rc::downgrade(&func);
+
+        let view_id = REGISTRY.with_borrow_mut(|registry| {
+            let view_id = registry.alloc_view_id();
+            registry.add_reactive_view(
+                view_id,
+                Rc::new(move |view_id| {
+                    let Some(func) = weak_func.upgrade() else {
+                        return false;
+                    };
+                    view_id.as_current_reactive(|| func());
+                    true
+                }),
+            );
+            view_id
+        });
+
+        let effect = Self { view_id, func };
+        effect.run();
+        effect
+    }
+
+    /// Run the function now, e.g. because an enclosing view was updated instead of the effect.
+    pub fn run(&self) {
+        self.view_id.as_current_reactive(|| (self.func)());
+    }
+}
+
+impl Drop for Effect {
+    fn drop(&mut self) {
+        REGISTRY.with_borrow_mut(|registry| {
+            registry.on_reactive_dropped(self.view_id);
+            registry.on_view_dropped(self.view_id);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use crate::{
+        platform::test_platform::TestPlatform, prelude::platform::use_state, reactive::State,
+        signal::dispatch_pending_signals, view::Func, View,
+    };
+
+    use super::*;
+
+    #[test]
+    fn runs_again_when_a_read_state_changes() {
+        REGISTRY.with_borrow_mut(|registry| {
+            registry.reset();
+            // Signals are only dispatched once the platform is initialized
+            registry.initialized = true;
+        });
+
+        let state: Rc<Cell<Option<State<i32>>>> = Rc::default();
+        let _view = <Func<_, ()> as View<TestPlatform, ()>>::init_diff(
+            Func(
+                {
+                    let state = state.clone();
+                    move || state.set(Some(use_state(|| 0)))
+                },
+                (),
+            ),
+            &mut (),
+        );
+        let state = state.get().unwrap();
+
+        let seen = Rc::new(Cell::new(-1));
+        let effect = Effect::new({
+            let seen = seen.clone();
+            move || seen.set(state.get())
+        });
+        assert_eq!(0, seen.get());
+
+        state.set(1);
+        dispatch_pending_signals();
+        assert_eq!(1, seen.get());
+
+        drop(effect);
+        state.set(2);
+        dispatch_pending_signals();
+        assert_eq!(1, seen.get());
+    }
+}
@@ -0,0 +1,113 @@
+//! Platform-agnostic event payloads, passed to event handlers.
+//!
+//! Platforms translate their native events into these types,
+//! so that handlers written once work the same everywhere.
+
+/// Modifier keys held down while an event happened.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub meta: bool,
+}
+
+impl Modifiers {
+    /// Ctrl, Alt or Meta is held down, so the key press is a command rather than text entry.
+    pub fn is_command(&self) -> bool {
+        self.ctrl || self.alt || self.meta
+    }
+}
+
+/// A click, or an activation from the keyboard.
+///
+/// Keyboard activations report the position `(0, 0)`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct ClickEvent {
+    pub x: i32,
+    pub y: i32,
+    pub modifiers: Modifiers,
+}
+
+/// A mouse event other than a click.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct MouseEvent {
+    pub x: i32,
+    pub y: i32,
+    pub modifiers: Modifiers,
+}
+
+/// A pointer event, from a mouse, pen or touch.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct PointerEvent {
+    pub x: i32,
+    pub y: i32,
+    /// Identifies the pointer, to tell simultaneous touches apart.
+    pub pointer_id: i32,
+    pub modifiers: Modifiers,
+}
+
+/// A key press or release.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct KeyEvent {
+    pub key: Key,
+    pub modifiers: Modifiers,
+}
+
+/// A logical key.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Key {
+    Char(char),
+    Enter,
+    Escape,
+    Backspace,
+    Delete,
+    Tab,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    F(u8),
+    Unidentified,
+}
+
+/// A change of the value of an input element.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InputEvent {
+    /// The new value of the element.
+    pub value: String,
+}
+
+/// A form being submitted.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct SubmitEvent;
+
+/// An element gaining or losing focus.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct FocusEvent;
+
+/// An element being scrolled.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct ScrollEvent {
+    /// The new horizontal scroll offset.
+    pub left: i32,
+    /// The new vertical scroll offset.
+    pub top: i32,
+}
+
+/// Any event payload, as passed to type-erased handlers.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum AnyEvent {
+    Click(ClickEvent),
+    Mouse(MouseEvent),
+    Pointer(PointerEvent),
+    Key(KeyEvent),
+    Input(InputEvent),
+    Submit(SubmitEvent),
+    Focus(FocusEvent),
+    Scroll(ScrollEvent),
+}
@@ -0,0 +1,185 @@
+//! Cross-platform focus management.
+//!
+//! A [FocusRef] is attached to an element with the `focus:ref` property.
+//! It can then move focus to that element or away from it,
+//! and reactively tells whether the element is focused.
+//!
+//! Related properties are `focus:autofocus`, which focuses an element when it's first shown,
+//! and `focus:trap`, which keeps focus within an element and its descendants.
+
+use std::{cell::RefCell, rc::Rc};
+
+use fnv::FnvHashMap;
+
+use crate::reactive::{use_state, State};
+
+thread_local! {
+    static TARGETS: RefCell<FnvHashMap<FocusRef, Rc<dyn FocusTarget>>> = RefCell::new(FnvHashMap::default());
+}
+
+/// Create a [FocusRef], to be attached to an element with `focus:ref`.
+pub fn use_focus_ref() -> FocusRef {
+    FocusRef {
+        focused: use_state(|| false),
+    }
+}
+
+/// A reference to a focusable element.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FocusRef {
+    focused: State<bool>,
+}
+
+impl FocusRef {
+    /// Move focus to the referenced element.
+    ///
+    /// Does nothing if the reference is not attached to any element.
+    pub fn focus(&self) {
+        if let Some(target) = self.target() {
+            target.focus();
+        }
+    }
+
+    /// Remove focus from the referenced element, if it has it.
+    pub fn blur(&self) {
+        if let Some(target) = self.target() {
+            target.blur();
+        }
+    }
+
+    /// Whether the referenced element is focused.
+    ///
+    /// This is a reactive read: Views calling it are updated when focus changes.
+    pub fn is_focused(&self) -> bool {
+        self.focused.get()
+    }
+
+    /// Called by platforms when the referenced element gains or loses focus.
+    ///
+    /// Returns `true` if that changed the focus state.
+    pub fn set_focused(&self, focused: bool) -> bool {
+        if self.focused.map(|current| *current == focused) {
+            return false;
+        }
+
+        self.focused.set(focused);
+        true
+    }
+
+    fn target(&self) -> Option<Rc<dyn FocusTarget>> {
+        TARGETS.with_borrow(|targets| targets.get(self).cloned())
+    }
+}
+
+impl std::fmt::Debug for FocusRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FocusRef").finish_non_exhaustive()
+    }
+}
+
+/// A platform's handle to a focusable element.
+pub trait FocusTarget {
+    fn focus(&self);
+    fn blur(&self);
+}
+
+/// Attach a [FocusRef] to a platform element.
+///
+/// The reference stays attached until the returned [Registration] is dropped.
+pub fn register(focus_ref: FocusRef, target: Rc<dyn FocusTarget>) -> Registration {
+    TARGETS.with_borrow_mut(|targets| {
+        targets.insert(focus_ref, target.clone());
+    });
+
+    Registration { focus_ref, target }
+}
+
+/// Keeps a [FocusRef] attached to a platform element.
+pub struct Registration {
+    focus_ref: FocusRef,
+    target: Rc<dyn FocusTarget>,
+}
+
+impl Registration {
+    pub fn focus_ref(&self) -> FocusRef {
+        self.focus_ref
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        TARGETS.with_borrow_mut(|targets| {
+            // The reference may since have been attached to another element
+            if targets
+                .get(&self.focus_ref)
+                .is_some_and(|target| Rc::ptr_eq(target, &self.target))
+            {
+                targets.remove(&self.focus_ref);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use crate::{
+        platform::test_platform::TestPlatform,
+        registry::{Registry, REGISTRY},
+        view::Func,
+        View,
+    };
+
+    use super::*;
+
+    #[derive(Default)]
+    struct MockTarget {
+        focused: Cell<bool>,
+    }
+
+    impl FocusTarget for MockTarget {
+        fn focus(&self) {
+            self.focused.set(true);
+        }
+
+        fn blur(&self) {
+            self.focused.set(false);
+        }
+    }
+
+    #[test]
+    fn focus_ref_is_routed_to_its_target() {
+        REGISTRY.with_borrow_mut(Registry::reset);
+
+        let focus_ref = Rc::new(Cell::new(None));
+        let _func_state = <Func<_, _> as View<TestPlatform, ()>>::init_diff(
+            Func(
+                {
+                    let focus_ref = focus_ref.clone();
+                    move || focus_ref.set(Some(use_focus_ref()))
+                },
+                (),
+            ),
+            &mut (),
+        );
+        let focus_ref = focus_ref.get().unwrap();
+
+        let target = Rc::new(MockTarget::default());
+        let registration = register(focus_ref, target.clone());
+
+        focus_ref.focus();
+        assert!(target.focused.get());
+        focus_ref.blur();
+        assert!(!target.focused.get());
+
+        assert!(!focus_ref.is_focused());
+        assert!(focus_ref.set_focused(true));
+        assert!(!focus_ref.set_focused(true));
+        assert!(focus_ref.is_focused());
+
+        drop(registration);
+        focus_ref.focus();
+        assert!(!target.focused.get());
+    }
+}
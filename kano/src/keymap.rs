@@ -0,0 +1,469 @@
+//! Application-level keyboard shortcuts.
+//!
+//! Shortcuts are key sequences like `"Ctrl+S"`, `"g g"` or `"?"`.
+//! They are bound either at app scope with [bind_global], or at component scope with [use_shortcut],
+//! which keeps the shortcut bound for as long as the component is shown.
+//!
+//! When several scopes bind the same keys, components take precedence over the app,
+//! and components rendered later (e.g. children, or a dialog that just opened) take precedence
+//! over those rendered earlier. Within one scope, a sequence can't be bound twice,
+//! and neither can a sequence that starts another one, since the shorter would always win.
+//!
+//! Platforms feed key presses into [dispatch].
+
+use std::{cell::RefCell, fmt::Display, rc::Rc};
+
+use crate::{
+    event::{Key, KeyEvent, Modifiers},
+    reactive::use_state,
+    registry::REGISTRY,
+    view_id::ViewId,
+};
+
+thread_local! {
+    static KEYMAP: RefCell<Keymap> = RefCell::new(Keymap::default());
+}
+
+/// A key together with the modifiers held down.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Chord {
+    pub key: Key,
+    pub modifiers: Modifiers,
+}
+
+impl Chord {
+    /// Parse a chord like `Ctrl+S`, `Shift+Tab`, `F1` or `?`.
+    pub fn parse(chord: &str) -> Result<Self, KeymapError> {
+        let invalid = || KeymapError::Parse(chord.to_string());
+
+        // `+` is a valid key on its own, as in `+` or `Ctrl++`
+        let (modifiers, key) = match chord.strip_suffix("++") {
+            _ if chord == "+" => (None, "+"),
+            Some(modifiers) => (Some(modifiers), "+"),
+            None => match chord.rsplit_once('+') {
+                Some((modifiers, key)) => (Some(modifiers), key),
+                None => (None, chord),
+            },
+        };
+
+        let mut parsed = Modifiers::default();
+        for modifier in modifiers
+            .into_iter()
+            .flat_map(|modifiers| modifiers.split('+'))
+        {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => parsed.ctrl = true,
+                "alt" | "option" => parsed.alt = true,
+                "shift" => parsed.shift = true,
+                "meta" | "cmd" | "super" => parsed.meta = true,
+                _ => {
+                    return Err(KeymapError::Modifier {
+                        sequence: chord.to_string(),
+                        modifier: modifier.to_string(),
+                    })
+                }
+            }
+        }
+
+        Ok(Self::normalized(
+            parse_key(key).ok_or_else(invalid)?,
+            parsed,
+        ))
+    }
+
+    /// The chord of a key press.
+    pub fn from_event(event: &KeyEvent) -> Self {
+        Self::normalized(event.key, event.modifiers)
+    }
+
+    /// Characters already tell whether shift was held (`?` and not `Shift+/`),
+    /// except together with other modifiers, where letters are compared case-insensitively.
+    fn normalized(key: Key, mut modifiers: Modifiers) -> Self {
+        let key = match key {
+            Key::Char(char) if modifiers.is_command() => Key::Char(char.to_ascii_lowercase()),
+            Key::Char(char) => {
+                modifiers.shift = false;
+                Key::Char(char)
+            }
+            key => key,
+        };
+
+        Self { key, modifiers }
+    }
+}
+
+fn parse_key(key: &str) -> Option<Key> {
+    let mut chars = key.chars();
+    if let (Some(char), None) = (chars.next(), chars.next()) {
+        return Some(Key::Char(char));
+    }
+
+    Some(match key.to_ascii_lowercase().as_str() {
+        "space" => Key::Char(' '),
+        "enter" | "return" => Key::Enter,
+        "esc" | "escape" => Key::Escape,
+        "backspace" => Key::Backspace,
+        "del" | "delete" => Key::Delete,
+        "tab" => Key::Tab,
+        "left" => Key::Left,
+        "right" => Key::Right,
+        "up" => Key::Up,
+        "down" => Key::Down,
+        "home" => Key::Home,
+        "end" => Key::End,
+        "pageup" => Key::PageUp,
+        "pagedown" => Key::PageDown,
+        other => Key::F(other.strip_prefix('f')?.parse().ok()?),
+    })
+}
+
+/// A sequence of chords, pressed one after the other.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct KeySequence(pub Vec<Chord>);
+
+impl KeySequence {
+    /// Parse a sequence of space-separated chords, like `g g`.
+    pub fn parse(sequence: &str) -> Result<Self, KeymapError> {
+        let chords = sequence
+            .split_whitespace()
+            .map(Chord::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if chords.is_empty() {
+            return Err(KeymapError::Parse(sequence.to_string()));
+        }
+
+        Ok(Self(chords))
+    }
+
+    fn starts_with(&self, prefix: &[Chord]) -> bool {
+        self.0.starts_with(prefix)
+    }
+}
+
+/// An error binding a shortcut.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum KeymapError {
+    /// The key sequence could not be parsed.
+    Parse(String),
+    /// A chord has a modifier that is empty, as in `Ctrl++S`, or that is not one of Ctrl, Alt, Shift or Meta.
+    Modifier { sequence: String, modifier: String },
+    /// The key sequence overlaps a sequence already bound in the same scope.
+    Conflict { sequence: String, existing: String },
+}
+
+impl Display for KeymapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse(sequence) => write!(f, "invalid key sequence `{sequence}`"),
+            Self::Modifier { sequence, modifier } if modifier.is_empty() => {
+                write!(f, "`{sequence}` has an empty modifier")
+            }
+            Self::Modifier { sequence, modifier } => write!(
+                f,
+                "`{modifier}` in `{sequence}` is not a modifier: use Ctrl, Alt, Shift or Meta"
+            ),
+            Self::Conflict { sequence, existing } => {
+                write!(
+                    f,
+                    "`{sequence}` conflicts with `{existing}` in the same scope"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for KeymapError {}
+
+/// Where a shortcut is bound.
+///
+/// Scopes that compare greater take precedence.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+enum Scope {
+    App,
+    Component(ViewId),
+}
+
+type ShortcutHandler = Rc<dyn Fn()>;
+
+struct Entry {
+    id: u64,
+    scope: Scope,
+    text: String,
+    sequence: KeySequence,
+    handler: ShortcutHandler,
+}
+
+#[derive(Default)]
+struct Keymap {
+    next_id: u64,
+    entries: Vec<Entry>,
+    /// The chords pressed so far of a sequence not yet completed.
+    pending: Vec<Chord>,
+}
+
+enum Resolution {
+    Exact(ShortcutHandler),
+    Prefix,
+    None,
+}
+
+impl Keymap {
+    fn bind(
+        &mut self,
+        scope: Scope,
+        text: &str,
+        handler: ShortcutHandler,
+    ) -> Result<u64, KeymapError> {
+        let sequence = KeySequence::parse(text)?;
+
+        if let Some(existing) = self.entries.iter().find(|entry| {
+            entry.scope == scope
+                && (entry.sequence.starts_with(&sequence.0)
+                    || sequence.starts_with(&entry.sequence.0))
+        }) {
+            return Err(KeymapError::Conflict {
+                sequence: text.to_string(),
+                existing: existing.text.clone(),
+            });
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.entries.push(Entry {
+            id,
+            scope,
+            text: text.to_string(),
+            sequence,
+            handler,
+        });
+
+        Ok(id)
+    }
+
+    fn unbind(&mut self, id: u64) {
+        self.entries.retain(|entry| entry.id != id);
+    }
+
+    /// Feed a chord, returning the handler to invoke, and whether the chord was consumed.
+    fn feed(&mut self, chord: Chord) -> (Option<ShortcutHandler>, bool) {
+        let mut candidate = std::mem::take(&mut self.pending);
+        candidate.push(chord);
+
+        let mut resolution = self.resolve(&candidate);
+        if let (Resolution::None, true) = (&resolution, candidate.len() > 1) {
+            // The sequence was broken off, but the chord may start another one
+            candidate = vec![chord];
+            resolution = self.resolve(&candidate);
+        }
+
+        match resolution {
+            Resolution::Exact(handler) => (Some(handler), true),
+            Resolution::Prefix => {
+                self.pending = candidate;
+                (None, true)
+            }
+            Resolution::None => (None, false),
+        }
+    }
+
+    fn resolve(&self, candidate: &[Chord]) -> Resolution {
+        let mut scopes: Vec<Scope> = self.entries.iter().map(|entry| entry.scope).collect();
+        scopes.sort_unstable_by(|a, b| b.cmp(a));
+        scopes.dedup();
+
+        for scope in scopes {
+            let mut in_scope = self.entries.iter().filter(|entry| entry.scope == scope);
+
+            if let Some(entry) = in_scope.clone().find(|entry| entry.sequence.0 == candidate) {
+                return Resolution::Exact(entry.handler.clone());
+            }
+
+            if in_scope.any(|entry| entry.sequence.starts_with(candidate)) {
+                return Resolution::Prefix;
+            }
+        }
+
+        Resolution::None
+    }
+}
+
+/// Bind a shortcut at app scope, for the lifetime of the app.
+pub fn bind_global(sequence: &str, handler: impl Fn() + 'static) -> Result<(), KeymapError> {
+    KEYMAP.with_borrow_mut(|keymap| keymap.bind(Scope::App, sequence, Rc::new(handler)))?;
+    Ok(())
+}
+
+/// Bind a shortcut at the scope of the current component, for as long as it is shown.
+///
+/// The sequence is bound when the component is first rendered, and kept on later renders,
+/// which replace the handler so that it sees the captures of the latest render.
+pub fn use_shortcut(sequence: &str, handler: impl Fn() + 'static) -> Result<(), KeymapError> {
+    let view_id = REGISTRY
+        .with_borrow(|registry| registry.current_func_view)
+        .expect("shortcuts should not be used outside the view hierarchy!");
+
+    let handler: ShortcutHandler = Rc::new(handler);
+    let binding = use_state(|| {
+        let current = Rc::new(RefCell::new(handler.clone()));
+        let bound = current.clone();
+
+        KEYMAP
+            .with_borrow_mut(|keymap| {
+                keymap.bind(
+                    Scope::Component(view_id),
+                    sequence,
+                    // Don't hold the borrow while the handler runs
+                    Rc::new(move || {
+                        let handler = bound.borrow().clone();
+                        handler()
+                    }),
+                )
+            })
+            .map(|id| Binding { id, current })
+    });
+
+    binding.map(|binding| match binding {
+        Ok(binding) => {
+            *binding.current.borrow_mut() = handler;
+            Ok(())
+        }
+        Err(error) => Err(error.clone()),
+    })
+}
+
+/// A component-scoped shortcut, unbound when dropped along with the component's state.
+struct Binding {
+    id: u64,
+    /// The handler of the latest render.
+    current: Rc<RefCell<ShortcutHandler>>,
+}
+
+impl Drop for Binding {
+    fn drop(&mut self) {
+        let id = self.id;
+        KEYMAP.with_borrow_mut(|keymap| keymap.unbind(id));
+    }
+}
+
+/// Feed a key press into the keymap, invoking the handler of a completed shortcut.
+///
+/// Returns `true` if the key press was consumed, either completing a shortcut or continuing one.
+pub fn dispatch(event: &KeyEvent) -> bool {
+    let (handler, consumed) =
+        KEYMAP.with_borrow_mut(|keymap| keymap.feed(Chord::from_event(event)));
+
+    // Don't hold the borrow while the handler runs
+    if let Some(handler) = handler {
+        handler();
+    }
+
+    consumed
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    fn chord(key: Key) -> Chord {
+        Chord {
+            key,
+            modifiers: Modifiers::default(),
+        }
+    }
+
+    fn counter(count: &Rc<Cell<u32>>) -> ShortcutHandler {
+        let count = count.clone();
+        Rc::new(move || count.set(count.get() + 1))
+    }
+
+    #[test]
+    fn parse_chords() {
+        assert_eq!(
+            Chord {
+                key: Key::Char('s'),
+                modifiers: Modifiers {
+                    ctrl: true,
+                    ..Default::default()
+                }
+            },
+            Chord::parse("Ctrl+S").unwrap()
+        );
+        assert_eq!(chord(Key::Char('?')), Chord::parse("?").unwrap());
+        assert_eq!(chord(Key::F(5)), Chord::parse("F5").unwrap());
+        assert_eq!(Key::Char('+'), Chord::parse("Alt++").unwrap().key);
+        assert_eq!(
+            KeySequence(vec![chord(Key::Char('g')), chord(Key::Char('g'))]),
+            KeySequence::parse("g g").unwrap()
+        );
+        assert!(Chord::parse("Hyper+x").is_err());
+    }
+
+    #[test]
+    fn parse_plus() {
+        assert_eq!(chord(Key::Char('+')), Chord::parse("+").unwrap());
+        assert_eq!(
+            Chord {
+                key: Key::Char('+'),
+                modifiers: Modifiers {
+                    ctrl: true,
+                    ..Default::default()
+                }
+            },
+            Chord::parse("Ctrl++").unwrap()
+        );
+        assert_eq!(
+            Err(KeymapError::Modifier {
+                sequence: "a+b".to_string(),
+                modifier: "a".to_string()
+            }),
+            Chord::parse("a+b")
+        );
+        for empty in ["+x", "++", "Ctrl++S"] {
+            assert_eq!(
+                format!("`{empty}` has an empty modifier"),
+                Chord::parse(empty).unwrap_err().to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn sequences_and_scope_precedence() {
+        let mut keymap = Keymap::default();
+        let (app, component) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+
+        keymap.bind(Scope::App, "g g", counter(&app)).unwrap();
+        keymap.bind(Scope::App, "?", counter(&app)).unwrap();
+        keymap
+            .bind(Scope::Component(ViewId(1)), "?", counter(&component))
+            .unwrap();
+
+        let (handler, consumed) = keymap.feed(chord(Key::Char('g')));
+        assert!(handler.is_none() && consumed);
+        keymap.feed(chord(Key::Char('g'))).0.unwrap()();
+        assert_eq!(1, app.get());
+
+        keymap.feed(chord(Key::Char('?'))).0.unwrap()();
+        assert_eq!(1, app.get());
+        assert_eq!(1, component.get());
+
+        assert!(!keymap.feed(chord(Key::Char('x'))).1);
+    }
+
+    #[test]
+    fn conflicts_within_scope() {
+        let mut keymap = Keymap::default();
+        let count = Rc::new(Cell::new(0));
+
+        keymap.bind(Scope::App, "g g", counter(&count)).unwrap();
+        assert!(matches!(
+            keymap.bind(Scope::App, "g", counter(&count)),
+            Err(KeymapError::Conflict { .. })
+        ));
+        keymap
+            .bind(Scope::Component(ViewId(1)), "g", counter(&count))
+            .unwrap();
+    }
+}
@@ -1,13 +1,19 @@
 //! Kano is a work-in-progress GUI application framework written for and in Rust.
 
 pub mod attr;
+pub mod effect;
+pub mod event;
+pub mod focus;
 pub mod history;
+pub mod keymap;
 pub mod markup;
 pub mod platform;
 pub mod prelude;
 pub mod property;
 pub mod reactive;
+pub mod root;
 pub mod view;
+pub mod viewport;
 
 #[cfg(feature = "vdom")]
 pub mod vdom;
@@ -139,9 +145,10 @@ impl<A, B: FromProperty<A>> FromProperty<Option<A>> for B {
     }
 }
 
-pub struct Init<P> {
+pub struct Init<P: Platform> {
     platform: PhantomData<P>,
     context: PlatformContext,
+    config: P::Config,
 }
 
 thread_local! {
@@ -180,15 +187,28 @@ pub fn init<P: Platform>() -> Init<P> {
     Init {
         platform: PhantomData,
         context,
+        config: Default::default(),
     }
 }
 
 impl<P: Platform> Init<P> {
+    /// Configure the platform before running the app.
+    pub fn config(mut self, config: P::Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Take the platform context and configuration, for drivers that run the app themselves,
+    /// e.g. to render it headlessly in tests.
+    pub fn into_context(self) -> (PlatformContext, P::Config) {
+        (self.context, self.config)
+    }
+
     pub fn run_app<V>(self, func: impl (Fn() -> V) + 'static) -> anyhow::Result<()>
     where
         V: View<P, P::Markup> + 'static,
     {
-        P::run(Reactive(func), self.context)
+        P::run(Reactive(func), self.context, self.config)
     }
 }
 
@@ -7,13 +7,21 @@ pub trait Platform: Sized + 'static {
     /// The root markup language on this platform.
     type Markup: Markup<Self>;
 
+    /// Platform-specific configuration, set with [crate::Init::config].
+    type Config: Default;
+
     /// Initialize the platform.
     fn init(init: PlatformInit) -> PlatformContext;
 
     /// Run an application on the platform.
     ///
+    /// The platform keeps the view as its [crate::root::Root] while the application runs.
     /// This function _may_ block indefinitely, depending on the platform.
-    fn run(view: impl View<Self, Self::Markup>, context: PlatformContext) -> anyhow::Result<()>;
+    fn run(
+        view: impl View<Self, Self::Markup> + 'static,
+        context: PlatformContext,
+        config: Self::Config,
+    ) -> anyhow::Result<()>;
 
     fn spawn_task(task: impl std::future::Future<Output = ()> + 'static);
 }
@@ -47,6 +55,7 @@ pub(crate) mod test_platform {
 
     impl super::Platform for TestPlatform {
         type Markup = ();
+        type Config = ();
 
         fn init(init: PlatformInit) -> PlatformContext {
             PlatformContext {
@@ -57,7 +66,11 @@ pub(crate) mod test_platform {
             }
         }
 
-        fn run(_view: impl View<Self, ()>, _context: PlatformContext) -> anyhow::Result<()> {
+        fn run(
+            _view: impl View<Self, ()> + 'static,
+            _context: PlatformContext,
+            _config: (),
+        ) -> anyhow::Result<()> {
             Ok(())
         }
 
@@ -80,6 +93,8 @@ pub(crate) mod test_platform {
         fn exit_children(&mut self) {}
         fn next_sibling(&mut self) {}
         fn remove(&mut self) {}
-        fn replace(&mut self, _func: impl FnOnce(&mut Self)) {}
+        fn replace(&mut self, func: impl FnOnce(&mut Self)) {
+            func(self)
+        }
     }
 }
@@ -1,11 +1,14 @@
 /// Prelude for apps which excludes platform-agnostic types.
 pub mod app {
+    pub use crate::focus::use_focus_ref;
+    pub use crate::keymap::use_shortcut;
     pub use crate::let_props;
     pub use crate::platform::Platform;
     pub use crate::property::*;
     pub use crate::reactive::*;
     pub use crate::view::Dyn;
     pub use crate::view::Fmt;
+    pub use crate::viewport::use_viewport;
     pub use kano_macros::view;
 }
 
@@ -9,13 +9,114 @@ pub fn to(location: impl Into<Cow<'static, str>>) -> crate::attr::To {
 pub mod on {
     use std::rc::Rc;
 
-    use crate::attr::*;
+    use crate::{attr::*, event::*};
 
-    pub fn click(func: impl Fn() + 'static) -> On<Click> {
+    pub fn click(func: impl Fn(&ClickEvent) + 'static) -> On<Click> {
         On::new(Click, Rc::new(func))
     }
 
-    pub fn mouseover(func: impl Fn() + 'static) -> On<MouseOver> {
+    pub fn mouseover(func: impl Fn(&MouseEvent) + 'static) -> On<MouseOver> {
         On::new(MouseOver, Rc::new(func))
     }
+
+    pub fn mouseenter(func: impl Fn(&MouseEvent) + 'static) -> On<MouseEnter> {
+        On::new(MouseEnter, Rc::new(func))
+    }
+
+    pub fn mouseleave(func: impl Fn(&MouseEvent) + 'static) -> On<MouseLeave> {
+        On::new(MouseLeave, Rc::new(func))
+    }
+
+    pub fn pointerdown(func: impl Fn(&PointerEvent) + 'static) -> On<PointerDown> {
+        On::new(PointerDown, Rc::new(func))
+    }
+
+    pub fn pointerup(func: impl Fn(&PointerEvent) + 'static) -> On<PointerUp> {
+        On::new(PointerUp, Rc::new(func))
+    }
+
+    pub fn pointermove(func: impl Fn(&PointerEvent) + 'static) -> On<PointerMove> {
+        On::new(PointerMove, Rc::new(func))
+    }
+
+    pub fn keydown(func: impl Fn(&KeyEvent) + 'static) -> On<KeyDown> {
+        On::new(KeyDown, Rc::new(func))
+    }
+
+    pub fn keyup(func: impl Fn(&KeyEvent) + 'static) -> On<KeyUp> {
+        On::new(KeyUp, Rc::new(func))
+    }
+
+    pub fn input(func: impl Fn(&InputEvent) + 'static) -> On<Input> {
+        On::new(Input, Rc::new(func))
+    }
+
+    pub fn change(func: impl Fn(&InputEvent) + 'static) -> On<Change> {
+        On::new(Change, Rc::new(func))
+    }
+
+    pub fn submit(func: impl Fn(&SubmitEvent) + 'static) -> On<Submit> {
+        On::new(Submit, Rc::new(func))
+    }
+
+    pub fn focus(func: impl Fn(&FocusEvent) + 'static) -> On<Focus> {
+        On::new(Focus, Rc::new(func))
+    }
+
+    pub fn blur(func: impl Fn(&FocusEvent) + 'static) -> On<Blur> {
+        On::new(Blur, Rc::new(func))
+    }
+
+    pub fn scroll(func: impl Fn(&ScrollEvent) + 'static) -> On<Scroll> {
+        On::new(Scroll, Rc::new(func))
+    }
+}
+
+/// Two-way binding properties
+pub mod bind {
+    use crate::{attr::Bind, reactive::State};
+
+    pub fn value(state: State<String>) -> Bind {
+        Bind::Value(state)
+    }
+
+    pub fn checked(state: State<bool>) -> Bind {
+        Bind::Checked(state)
+    }
+}
+
+/// Text input properties
+pub mod input {
+    use std::borrow::Cow;
+
+    use crate::attr::InputAttr;
+
+    pub fn placeholder(text: impl Into<Cow<'static, str>>) -> InputAttr {
+        InputAttr::Placeholder(text.into())
+    }
+
+    pub fn password(enabled: bool) -> InputAttr {
+        InputAttr::Password(enabled)
+    }
+}
+
+/// Focus management properties
+pub mod focus {
+    use crate::{attr::FocusAttr, focus::FocusRef};
+
+    pub fn r#ref(focus_ref: FocusRef) -> FocusAttr {
+        FocusAttr::Ref(focus_ref)
+    }
+
+    pub fn autofocus(enabled: bool) -> FocusAttr {
+        FocusAttr::Autofocus(enabled)
+    }
+
+    pub fn trap(enabled: bool) -> FocusAttr {
+        FocusAttr::Trap(enabled)
+    }
+
+    pub fn tabindex(index: i32) -> FocusAttr {
+        FocusAttr::TabIndex(index)
+    }
 }
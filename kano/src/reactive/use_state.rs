@@ -104,6 +104,21 @@ impl<T> Clone for State<T> {
 
 impl<T> Copy for State<T> {}
 
+/// Two states are equal when they refer to the same signal.
+impl<T> PartialEq for State<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.signal == other.signal
+    }
+}
+
+impl<T> Eq for State<T> {}
+
+impl<T> std::hash::Hash for State<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.signal.hash(state);
+    }
+}
+
 pub struct Ref<T> {
     ref_cell: Rc<RefCell<dyn std::any::Any>>,
     phantom: PhantomData<T>,
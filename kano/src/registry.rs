@@ -10,6 +10,7 @@ use fnv::{FnvHashMap, FnvHashSet};
 use crate::history::{HistoryAPI, HistoryState};
 use crate::signal::Signal;
 use crate::view_id::ViewId;
+use crate::viewport::Viewport;
 
 pub type ViewCallback = Rc<dyn Fn(ViewId) -> bool>;
 
@@ -39,6 +40,8 @@ pub(crate) struct Registry {
 pub(crate) struct Globals {
     pub history_api: Rc<dyn HistoryAPI>,
     pub history_signal: Signal,
+    pub viewport: Viewport,
+    pub viewport_signal: Signal,
 }
 
 pub(crate) struct ReactiveEntry {
@@ -56,10 +59,13 @@ impl Registry {
         let mut next_signal_id = 0;
 
         let history_signal = Signal(fetch_add(&mut next_signal_id, 1));
+        let viewport_signal = Signal(fetch_add(&mut next_signal_id, 1));
 
         let globals = Globals {
             history_api: Rc::new(HistoryState::new("".to_string())),
             history_signal,
+            viewport: Viewport::default(),
+            viewport_signal,
         };
 
         Self {
@@ -0,0 +1,119 @@
+//! The root view of a running app.
+
+use std::{any::Any, cell::RefCell};
+
+use crate::{markup::Markup, platform::Platform, registry::REGISTRY, view::Dyn, View};
+
+thread_local! {
+    /// A view waiting to replace the root view.
+    static REPLACEMENT: RefCell<Option<Box<dyn Any>>> = RefCell::new(None);
+}
+
+/// The root of a running app, which the platform keeps for as long as the app runs.
+///
+/// Dropping the root drops the state of every view in the app,
+/// releasing their registry entries and event listeners.
+pub struct Root<P: Platform> {
+    state: Box<dyn Any>,
+    cursor: <P::Markup as Markup<P>>::Cursor,
+}
+
+impl<P: Platform> Root<P> {
+    /// Render `view` at the cursor.
+    pub fn new(
+        view: impl View<P, P::Markup> + 'static,
+        mut cursor: <P::Markup as Markup<P>>::Cursor,
+    ) -> Self {
+        let state = Dyn::new(view).init_diff(&mut cursor);
+        Self { state, cursor }
+    }
+
+    /// The cursor positioned at the root view.
+    pub fn cursor(&self) -> &<P::Markup as Markup<P>>::Cursor {
+        &self.cursor
+    }
+
+    /// Diff the root view with the view passed to [replace], if any.
+    ///
+    /// Returns whether the root view was replaced.
+    pub fn apply_replacement(&mut self) -> bool {
+        let Some(replacement) = REPLACEMENT.take() else {
+            return false;
+        };
+        let Ok(view) = replacement.downcast::<Dyn<P, P::Markup>>() else {
+            return false;
+        };
+
+        view.diff(&mut self.state, &mut self.cursor);
+        true
+    }
+}
+
+/// Replace the root view of the running app, e.g. to hot-reload it or to switch between apps.
+///
+/// The platform diffs the new view against the current one when it dispatches signals next,
+/// so a view of the same type keeps its state, while a view of another type starts afresh.
+pub fn replace<P: Platform>(view: impl View<P, P::Markup> + 'static) {
+    REPLACEMENT.set(Some(Box::new(Dyn::<P, P::Markup>::new(view))));
+
+    let tick_fn = REGISTRY.with_borrow(|registry| registry.platform_on_signal_tick.clone());
+    if let Some(tick_fn) = tick_fn {
+        tick_fn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use crate::{
+        platform::test_platform::TestPlatform,
+        prelude::platform::use_state,
+        registry::{Registry, REGISTRY},
+        view::{Func, Reactive},
+    };
+
+    use super::*;
+
+    /// Sets a flag when dropped.
+    struct DropFlag(Rc<Cell<bool>>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    /// A view with state that sets `dropped` when dropped.
+    fn guarded(dropped: &Rc<Cell<bool>>) -> Func<impl FnOnce(), ()> {
+        let dropped = dropped.clone();
+        Func(
+            move || {
+                use_state(|| DropFlag(dropped));
+            },
+            (),
+        )
+    }
+
+    #[test]
+    fn replacing_and_dropping_the_root_drops_view_state() {
+        REGISTRY.with_borrow_mut(Registry::reset);
+
+        let first_dropped = Rc::new(Cell::new(false));
+        let mut root = Root::<TestPlatform>::new(guarded(&first_dropped), ());
+        assert!(!root.apply_replacement());
+
+        // Another view of another type replaces the first one
+        let second_dropped = Rc::new(Cell::new(false));
+        replace::<TestPlatform>(Reactive({
+            let second_dropped = second_dropped.clone();
+            move || guarded(&second_dropped)
+        }));
+        assert!(root.apply_replacement());
+        assert!(first_dropped.get());
+        assert!(!second_dropped.get());
+
+        drop(root);
+        assert!(second_dropped.get());
+    }
+}
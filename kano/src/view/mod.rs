@@ -2,6 +2,7 @@ mod constant;
 mod dynamic;
 mod either;
 mod func;
+mod one_of;
 mod reactive;
 mod seq;
 mod text;
@@ -12,6 +13,9 @@ pub use constant::Const;
 pub use dynamic::Dyn;
 pub use either::Either;
 pub use func::Func;
+pub use one_of::{
+    OneOf10, OneOf11, OneOf12, OneOf3, OneOf4, OneOf5, OneOf6, OneOf7, OneOf8, OneOf9,
+};
 pub use reactive::Reactive;
 pub use seq::seq_map;
 pub use seq::Map;
@@ -0,0 +1,134 @@
+//! N-ary sum views, used for `match` expressions with more than two arms.
+
+use crate::{
+    markup::{Cursor, Markup},
+    View,
+};
+
+macro_rules! one_of {
+    ($name:ident, $(($t:ident, $v:ident)),+) => {
+        /// A view that is one of several possible views, each with its own state.
+        #[derive(Clone, Copy)]
+        pub enum $name<$($t),+> {
+            $($v($t)),+
+        }
+
+        impl<P, M: Markup<P>, $($t: View<P, M>),+> View<P, M> for $name<$($t),+> {
+            type ConstState = $name<$($t::ConstState),+>;
+            type DiffState = $name<$($t::DiffState),+>;
+
+            fn init_const(self, cursor: &mut M::Cursor) -> Self::ConstState {
+                match self {
+                    $(Self::$v(view) => $name::$v(view.init_const(cursor))),+
+                }
+            }
+
+            fn init_diff(self, cursor: &mut M::Cursor) -> Self::DiffState {
+                match self {
+                    $(Self::$v(view) => $name::$v(view.init_diff(cursor))),+
+                }
+            }
+
+            fn diff(self, state: &mut Self::DiffState, cursor: &mut M::Cursor) {
+                match (self, state) {
+                    $(
+                        (Self::$v(view), $name::$v(view_state)) => {
+                            view.diff(view_state, cursor);
+                        }
+                    )+
+                    (view, state) => cursor.replace(|cursor| {
+                        *state = view.init_diff(cursor);
+                    }),
+                }
+            }
+        }
+    };
+}
+
+one_of!(OneOf3, (T0, V0), (T1, V1), (T2, V2));
+one_of!(OneOf4, (T0, V0), (T1, V1), (T2, V2), (T3, V3));
+one_of!(OneOf5, (T0, V0), (T1, V1), (T2, V2), (T3, V3), (T4, V4));
+one_of!(
+    OneOf6,
+    (T0, V0),
+    (T1, V1),
+    (T2, V2),
+    (T3, V3),
+    (T4, V4),
+    (T5, V5)
+);
+one_of!(
+    OneOf7,
+    (T0, V0),
+    (T1, V1),
+    (T2, V2),
+    (T3, V3),
+    (T4, V4),
+    (T5, V5),
+    (T6, V6)
+);
+one_of!(
+    OneOf8,
+    (T0, V0),
+    (T1, V1),
+    (T2, V2),
+    (T3, V3),
+    (T4, V4),
+    (T5, V5),
+    (T6, V6),
+    (T7, V7)
+);
+one_of!(
+    OneOf9,
+    (T0, V0),
+    (T1, V1),
+    (T2, V2),
+    (T3, V3),
+    (T4, V4),
+    (T5, V5),
+    (T6, V6),
+    (T7, V7),
+    (T8, V8)
+);
+one_of!(
+    OneOf10,
+    (T0, V0),
+    (T1, V1),
+    (T2, V2),
+    (T3, V3),
+    (T4, V4),
+    (T5, V5),
+    (T6, V6),
+    (T7, V7),
+    (T8, V8),
+    (T9, V9)
+);
+one_of!(
+    OneOf11,
+    (T0, V0),
+    (T1, V1),
+    (T2, V2),
+    (T3, V3),
+    (T4, V4),
+    (T5, V5),
+    (T6, V6),
+    (T7, V7),
+    (T8, V8),
+    (T9, V9),
+    (T10, V10)
+);
+one_of!(
+    OneOf12,
+    (T0, V0),
+    (T1, V1),
+    (T2, V2),
+    (T3, V3),
+    (T4, V4),
+    (T5, V5),
+    (T6, V6),
+    (T7, V7),
+    (T8, V8),
+    (T9, V9),
+    (T10, V10),
+    (T11, V11)
+);
@@ -0,0 +1,93 @@
+//! The size of the viewport the app is shown in.
+
+use crate::registry::REGISTRY;
+
+/// The size of the viewport: columns and rows in a terminal, CSS pixels on the web.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Viewport {
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Read the viewport size, re-rendering the calling view when it changes.
+///
+/// ```ignore
+/// let columns = if use_viewport().width >= 100 { 2 } else { 1 };
+/// ```
+pub fn use_viewport() -> Viewport {
+    let (signal, viewport) = REGISTRY
+        .with_borrow(|registry| (registry.globals.viewport_signal, registry.globals.viewport));
+    signal.register_reactive_dependency();
+    viewport
+}
+
+/// Set the viewport size. Platforms call this when the viewport is resized.
+pub fn set_viewport(viewport: Viewport) {
+    let signal = REGISTRY.with_borrow_mut(|registry| {
+        let globals = &mut registry.globals;
+        if globals.viewport == viewport {
+            return None;
+        }
+        globals.viewport = viewport;
+        Some(globals.viewport_signal)
+    });
+
+    if let Some(signal) = signal {
+        signal.send();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        platform::test_platform::TestPlatform, signal::dispatch_pending_signals, view::Reactive,
+        View,
+    };
+
+    use super::*;
+
+    #[test]
+    fn views_re_render_when_the_viewport_is_resized() {
+        REGISTRY.with_borrow_mut(|registry| {
+            registry.reset();
+            // Signals are only dispatched once the platform is initialized
+            registry.initialized = true;
+        });
+        set_viewport(Viewport {
+            width: 80,
+            height: 24,
+        });
+
+        let seen: Rc<RefCell<Vec<Viewport>>> = Rc::default();
+        let _state = <Reactive<_> as View<TestPlatform, ()>>::init_diff(
+            Reactive({
+                let seen = seen.clone();
+                move || seen.borrow_mut().push(use_viewport())
+            }),
+            &mut (),
+        );
+
+        let resized = Viewport {
+            width: 100,
+            height: 30,
+        };
+        set_viewport(resized);
+        dispatch_pending_signals();
+        // Setting the same size again is not a change
+        set_viewport(resized);
+        dispatch_pending_signals();
+
+        assert_eq!(
+            vec![
+                Viewport {
+                    width: 80,
+                    height: 24
+                },
+                resized
+            ],
+            *seen.borrow()
+        );
+    }
+}